@@ -0,0 +1,36 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use num_bigint::BigUint;
+use elliptic::curves::named::P256;
+
+// average time of `f` over `iterations` runs
+fn bench(name: &str, iterations: u32, f: impl Fn()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    let average = start.elapsed() / iterations;
+    println!("{name:>16}: {average:?}");
+    average
+}
+
+fn main() {
+    let field = P256.field();
+    let e = P256.curve(&field);
+    let g = e.get_base();
+    let k = BigUint::parse_bytes(b"C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721", 16).unwrap();
+
+    let double_and_add = bench("double_and_add", 20, || {
+        black_box(e.double_and_add(k.clone(), g.clone()));
+    });
+    let wnaf = bench("wnaf_mul", 20, || {
+        black_box(e.wnaf_mul(k.clone(), g.clone()));
+    });
+    bench("mul", 20, || {
+        black_box(e.mul(k.clone(), g.clone()));
+    });
+    bench("complete_mul", 20, || {
+        black_box(e.complete_mul(k.clone(), g.clone()));
+    });
+    println!("wnaf_mul speedup over double_and_add: {:.1}x", double_and_add.as_secs_f64() / wnaf.as_secs_f64());
+}
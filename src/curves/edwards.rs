@@ -0,0 +1,412 @@
+use num_bigint::BigUint;
+use crate::curves::montgomery::MontgomeryCurve;
+use crate::curves::point::Point;
+use crate::curves::weierstass::WeierstrassCurve;
+use crate::error::Error;
+use crate::field::field::Field;
+use crate::field::field_element::FieldElement;
+use crate::utils::bit_iter::BitIter;
+
+// extended coordinates `(X:Y:T:Z)` with `x = X/Z`, `y = Y/Z` and `xy = T/Z`
+#[derive(Debug, Clone)]
+pub struct EdwardsPoint<'a> {
+    pub x: FieldElement<'a>,
+    pub y: FieldElement<'a>,
+    pub t: FieldElement<'a>,
+    pub z: FieldElement<'a>,
+}
+
+impl<'a> EdwardsPoint<'a> {
+    // `(0, 1)` is the neutral element, it's an affine point on twisted edwards curves
+    pub fn identity(f: &'a Field) -> Self {
+        Self {
+            x: f.zero(),
+            y: f.one(),
+            t: f.zero(),
+            z: f.one(),
+        }
+    }
+
+    pub fn from_affine(x: FieldElement<'a>, y: FieldElement<'a>) -> Self {
+        Self {
+            t: x.clone() * y.clone(),
+            z: x.field.one(),
+            x,
+            y,
+        }
+    }
+
+    pub fn to_affine(&self) -> (FieldElement<'a>, FieldElement<'a>) {
+        let z_inv = self.z.clone().inverse();
+        (self.x.clone() * z_inv.clone(), self.y.clone() * z_inv)
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.x.is_zero() && self.y == self.z
+    }
+}
+
+// `X1/Z1 = X2/Z2` and `Y1/Z1 = Y2/Z2` without inversions, `T` follows from the other coordinates
+impl PartialEq for EdwardsPoint<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x.clone() * other.z.clone() == other.x.clone() * self.z.clone()
+            && self.y.clone() * other.z.clone() == other.y.clone() * self.z.clone()
+    }
+}
+
+impl std::ops::Neg for EdwardsPoint<'_> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: self.y,
+            t: -self.t,
+            z: self.z,
+        }
+    }
+}
+
+// ax^2 + y^2 = 1 + dx^2y^2
+pub struct TwistedEdwardsCurve<'a> {
+    a: FieldElement<'a>,
+    d: FieldElement<'a>,
+}
+
+// https://www.hyperelliptic.org/EFD/g1p/auto-twisted-extended.html
+impl<'a> TwistedEdwardsCurve<'a> {
+    pub fn new (
+        a: FieldElement<'a>,
+        d: FieldElement<'a>,
+    ) -> Self {
+        assert_eq!(a.field, d.field, "should be in the same field");
+        Self {
+            a,
+            d,
+        }
+    }
+
+    // same as `new` but rejects singular curves, `ad(a - d) = 0`
+    pub fn try_new (
+        a: FieldElement<'a>,
+        d: FieldElement<'a>,
+    ) -> Result<Self, Error> {
+        if a.field != d.field {
+            return Err(Error::FieldMismatch);
+        }
+        if (a.clone() * d.clone() * (a.clone() - d.clone())).is_zero() {
+            return Err(Error::SingularCurve);
+        }
+        Ok(Self {
+            a,
+            d,
+        })
+    }
+
+    // `a = (A + 2)/B`, `d = (A - 2)/B`
+    pub fn from_montgomery(curve: &MontgomeryCurve<'a>) -> Self {
+        let two = curve.a().field.get(BigUint::from(2_u8));
+        Self {
+            a: (curve.a().clone() + two.clone()) / curve.b().clone(),
+            d: (curve.a().clone() - two) / curve.b().clone(),
+        }
+    }
+
+    // -x^2 + y^2 = 1 - (121665/121666)x^2y^2 over `2^255 - 19`
+    pub fn ed25519(field: &'a Field) -> Self {
+        Self {
+            a: -field.one(),
+            d: -field.get(BigUint::from(121665_u32)) / field.get(BigUint::from(121666_u32)),
+        }
+    }
+
+    pub fn a(&self) -> &FieldElement<'a> {
+        &self.a
+    }
+
+    pub fn d(&self) -> &FieldElement<'a> {
+        &self.d
+    }
+
+    // addition formulas are complete iff `a` is a square and `d` is not,
+    // then there are no exceptional points even for doubling or neutral element
+    pub fn is_complete(&self) -> bool {
+        self.a.is_square() && !self.d.is_square()
+    }
+
+    pub fn contains(&self, p: &EdwardsPoint<'a>) -> bool {
+        if p.z.is_zero() || p.x.clone() * p.y.clone() != p.t.clone() * p.z.clone() {
+            return false;
+        }
+        let (x, y) = p.to_affine();
+        let (xx, yy) = (x.clone() * x, y.clone() * y);
+        self.a.clone() * xx.clone() + yy.clone() == self.a.field.one() + self.d.clone() * xx * yy
+    }
+
+    // add-2008-hwcd
+    pub fn point_add(
+        &self,
+        p1: EdwardsPoint<'a>,
+        p2: EdwardsPoint<'a>,
+    ) -> EdwardsPoint<'a> {
+        let a = p1.x.clone() * p2.x.clone();
+        let b = p1.y.clone() * p2.y.clone();
+        let c = p1.t * self.d.clone() * p2.t;
+        let d = p1.z * p2.z;
+        let e = (p1.x + p1.y) * (p2.x + p2.y) - a.clone() - b.clone();
+        let f = d.clone() - c.clone();
+        let g = d + c;
+        let h = b - self.a.clone() * a;
+        EdwardsPoint {
+            x: e.clone() * f.clone(),
+            y: g.clone() * h.clone(),
+            t: e * h,
+            z: f * g,
+        }
+    }
+
+    // dbl-2008-hwcd
+    pub fn point_double(
+        &self,
+        p1: EdwardsPoint<'a>,
+    ) -> EdwardsPoint<'a> {
+        let two = self.a.field.get(BigUint::from(2_u8));
+        let a = p1.x.clone() * p1.x.clone();
+        let b = p1.y.clone() * p1.y.clone();
+        let c = two * p1.z.clone() * p1.z;
+        let d = self.a.clone() * a.clone();
+        let xy = p1.x + p1.y;
+        let e = xy.clone() * xy - a - b.clone();
+        let g = d.clone() + b.clone();
+        let f = g.clone() - c;
+        let h = d - b;
+        EdwardsPoint {
+            x: e.clone() * f.clone(),
+            y: g.clone() * h.clone(),
+            t: e * h,
+            z: f * g,
+        }
+    }
+
+    pub fn double_and_add(
+        &self,
+        k: BigUint,
+        p1: EdwardsPoint<'a>,
+    ) -> EdwardsPoint<'a> {
+        let mut r0 = EdwardsPoint::identity(self.a.field);
+        let mut r1 = p1;
+
+        for digit in k.iter_u32_digits() {
+            for b in BitIter::from(digit).rev() {
+                if b {
+                    r0 = self.point_add(r0, r1.clone());
+                }
+                r1 = self.point_double(r1);
+            }
+        }
+
+        r0
+    }
+
+    // `By^2 = x^3 + Ax^2 + x` where `A = 2(a + d)/(a - d)` and `B = 4/(a - d)`
+    pub fn to_montgomery(&self) -> MontgomeryCurve<'a> {
+        let field = self.a.field;
+        let two = field.get(BigUint::from(2_u8));
+        let four = field.get(BigUint::from(4_u8));
+        let diff = self.a.clone() - self.d.clone();
+        MontgomeryCurve::new(
+            two * (self.a.clone() + self.d.clone()) / diff.clone(),
+            four / diff,
+        )
+    }
+
+    // `(x, y) -> ((1 + y)/(1 - y), (1 + y)/((1 - y)x))`,
+    // neutral element maps to point of infinity and `(0, -1)` to `(0, 0)`
+    pub fn map_to_montgomery(&self, p: EdwardsPoint<'a>) -> Point<'a> {
+        let field = self.a.field;
+        let (x, y) = p.to_affine();
+        if x.is_zero() {
+            if y.is_one() {
+                return Point::infinity(field);
+            }
+            return Point {
+                x: field.zero(),
+                y: field.zero(),
+                z: field.one(),
+            };
+        }
+        let u = (field.one() + y.clone()) / (field.one() - y);
+        Point {
+            y: u.clone() / x,
+            x: u,
+            z: field.one(),
+        }
+    }
+
+    // `(u, v) -> (u/v, (u - 1)/(u + 1))`, inverse of `map_to_montgomery`.
+    // `(0, 0)` maps to `(0, -1)`. Points with `v = 0, u != 0` or `u = -1` exist only when `d` is a square
+    // and correspond to points at infinity of the edwards curve, not to its neutral element
+    pub fn map_from_montgomery(&self, p: Point<'a>) -> Result<EdwardsPoint<'a>, Error> {
+        let field = self.a.field;
+        if p.is_infinity() {
+            return Ok(EdwardsPoint::identity(field));
+        }
+        let (u, v) = (p.x / p.z.clone(), p.y / p.z);
+        if u.is_zero() {
+            return Ok(EdwardsPoint::from_affine(field.zero(), -field.one()));
+        }
+        if v.is_zero() || (u.clone() + field.one()).is_zero() {
+            return Err(Error::ExceptionalPoint);
+        }
+        Ok(EdwardsPoint::from_affine(
+            u.clone() / v,
+            (u.clone() - field.one()) / (u + field.one()),
+        ))
+    }
+
+    pub fn to_weierstrass(&self) -> WeierstrassCurve<'a> {
+        self.to_montgomery().to_weierstrass()
+    }
+
+    pub fn map_to_weierstrass(&self, p: EdwardsPoint<'a>) -> Point<'a> {
+        self.to_montgomery().map_to_weierstrass(self.map_to_montgomery(p))
+    }
+
+    pub fn map_from_weierstrass(&self, p: Point<'a>) -> Result<EdwardsPoint<'a>, Error> {
+        self.map_from_montgomery(self.to_montgomery().map_from_weierstrass(p))
+    }
+}
+
+// p = 2^255 - 19, shared with Curve25519 and X25519
+pub fn ed25519_prime() -> BigUint {
+    (BigUint::from(1_u8) << 255) - BigUint::from(19_u8)
+}
+
+// order of the prime subgroup generated by base point, `2^252 + 27742317777372353535851937790883648493`
+pub fn ed25519_order() -> BigUint {
+    (BigUint::from(1_u8) << 252) + BigUint::from(27742317777372353535851937790883648493_u128)
+}
+
+pub const ED25519_COFACTOR: u8 = 8;
+
+// `(x, 4/5)` with even `x`
+pub fn ed25519_base_point(field: &Field) -> EdwardsPoint<'_> {
+    EdwardsPoint::from_affine(
+        field.get(BigUint::parse_bytes(b"15112221349535400772501151409588531511454012693041857206046113283949847762202", 10).unwrap()),
+        field.get(BigUint::from(4_u8)) / field.get(BigUint::from(5_u8)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use crate::curves::edwards::*;
+    use crate::field::field::Field;
+    use crate::test_utils::affine_points;
+
+    fn all_points<'a>(field: &'a Field, e: &TwistedEdwardsCurve<'a>) -> Vec<EdwardsPoint<'a>> {
+        affine_points(field, |x, y| e.contains(&EdwardsPoint::from_affine(x.clone(), y.clone())))
+            .into_iter()
+            .map(|(x, y)| EdwardsPoint::from_affine(x, y))
+            .collect()
+    }
+
+    #[test]
+    fn complete_addition() {
+        let field = Field::new(BigUint::from(61_u8));
+        // 1 is a square and 2 is not
+        let e = TwistedEdwardsCurve::new(field.one(), field.get(BigUint::from(2_u8)));
+        assert!(e.is_complete());
+
+        let points = all_points(&field, &e);
+        assert_eq!(points.len(), 72);
+
+        let m = e.to_montgomery();
+        let three = field.get(BigUint::from(3_u8));
+        for p in &points {
+            let scaled = EdwardsPoint {
+                x: p.x.clone() * three.clone(),
+                y: p.y.clone() * three.clone(),
+                t: p.t.clone() * three.clone(),
+                z: p.z.clone() * three.clone(),
+            };
+            assert_eq!(&scaled, p);
+            assert!(e.point_add(p.clone(), -p.clone()).is_identity());
+            assert_eq!(e.point_double(p.clone()), e.point_add(p.clone(), p.clone()));
+            assert_eq!(e.map_from_montgomery(e.map_to_montgomery(p.clone())), Ok(p.clone()));
+            for q in &points {
+                // unified formula never hits an exceptional case
+                let pq = e.point_add(p.clone(), q.clone());
+                assert!(e.contains(&pq));
+                assert_eq!(
+                    e.map_to_montgomery(pq),
+                    m.point_add(e.map_to_montgomery(p.clone()), e.map_to_montgomery(q.clone())),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn exceptional_montgomery_points() {
+        let field = Field::new(BigUint::from(61_u8));
+        // `d = 4` is a square, montgomery form has `u^2 + Au + 1 = 0` roots and a point with `u = -1`
+        let e = TwistedEdwardsCurve::new(field.one(), field.get(BigUint::from(4_u8)));
+        assert!(!e.is_complete());
+        let m = e.to_montgomery();
+        let on_curve = |u: &FieldElement, v: &FieldElement| {
+            m.b().clone() * v.clone() * v.clone() == u.clone() * u.clone() * u.clone() + m.a().clone() * u.clone() * u.clone() + u.clone()
+        };
+
+        let exceptional: Vec<_> = affine_points(&field, on_curve)
+            .into_iter()
+            .filter(|(u, v)| !u.is_zero() && (v.is_zero() || (u.clone() + field.one()).is_zero()))
+            .collect();
+        assert!(exceptional.iter().any(|(_, v)| v.is_zero()));
+        assert!(exceptional.iter().any(|(u, _)| (u.clone() + field.one()).is_zero()));
+        for (u, v) in exceptional {
+            let p = Point { x: u, y: v, z: field.one() };
+            assert_eq!(e.map_from_montgomery(p), Err(Error::ExceptionalPoint));
+        }
+    }
+
+    #[test]
+    fn to_weierstrass() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = TwistedEdwardsCurve::new(field.one(), field.get(BigUint::from(2_u8)));
+        let w = e.to_weierstrass();
+        assert_eq!(w.points_count(), Ok(BigUint::from(72_u8)));
+
+        let points = all_points(&field, &e);
+        for p in &points {
+            let mapped = e.map_to_weierstrass(p.clone());
+            assert_eq!(e.map_from_weierstrass(mapped.clone()), Ok(p.clone()));
+            for q in &points {
+                assert_eq!(
+                    e.map_to_weierstrass(e.point_add(p.clone(), q.clone())),
+                    w.point_add(mapped.clone(), e.map_to_weierstrass(q.clone())),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ed25519() {
+        let field = Field::new(ed25519_prime());
+        let e = TwistedEdwardsCurve::ed25519(&field);
+        assert!(e.is_complete());
+
+        let base = ed25519_base_point(&field);
+        assert!(e.contains(&base));
+        assert!(e.double_and_add(ed25519_order(), base.clone()).is_identity());
+
+        // birationally equivalent to curve25519 with base point `u = 9`
+        let m = e.to_montgomery();
+        assert_eq!(m.a(), &field.get(BigUint::from(486662_u32)));
+        assert_eq!(e.map_to_montgomery(base.clone()).x, field.get(BigUint::from(9_u8)));
+
+        // rfc8032 7.1 test 1, public key is `[s]B` where `s` is a clamped hash of secret key
+        let s = BigUint::parse_bytes(b"36144925721603087658594284515452164870581325872720374094707712194495455132720", 10).unwrap();
+        let (x, y) = e.double_and_add(s, base).to_affine();
+        assert_eq!(y.value, BigUint::parse_bytes(b"11903303657706407974989296177215005343713679411332034699907763981919547054807", 10).unwrap());
+        assert!(!x.value.bit(0));
+    }
+}
@@ -0,0 +1,306 @@
+use num_bigint::BigUint;
+use crate::curves::jacobian::JacobianPoint;
+use crate::curves::point::{AffinePoint, Point};
+use crate::curves::weierstass::WeierstrassCurve;
+use crate::error::Error;
+use crate::field::field_element::FieldElement;
+
+// windowed table for a base point that is multiplied many times, e.g. a generator.
+// Row `i` holds `j * 2^(wi) * P` for `j` in `1..2^w`, so `[k]P` is a sum of one entry
+// per `w`-bit window of `k` and needs no doublings.
+// Costs `ceil(bits / w) * (2^w - 1)` points of memory, `w = 4` takes 960 points for 256-bit scalars
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedBaseTable<'a> {
+    width: usize,
+    bits: u64,
+    rows: Vec<Vec<AffinePoint<'a>>>,
+}
+
+impl<'a> FixedBaseTable<'a> {
+    // covers scalars up to the order of the curve, or `p + 1 + 2√p` if the order is unknown
+    pub fn new(
+        curve: &WeierstrassCurve<'a>,
+        base: Point<'a>,
+        width: usize,
+    ) -> Self {
+        Self::with_bits(curve, base, width, curve.scalar_bits())
+    }
+
+    // same as `new` but rejects bases that do not pass `WeierstrassCurve::validate`
+    pub fn checked_new(
+        curve: &WeierstrassCurve<'a>,
+        base: Point<'a>,
+        width: usize,
+    ) -> Result<Self, Error> {
+        curve.validate(&base)?;
+        Ok(Self::new(curve, base, width))
+    }
+
+    pub fn with_bits(
+        curve: &WeierstrassCurve<'a>,
+        base: Point<'a>,
+        width: usize,
+        bits: u64,
+    ) -> Self {
+        assert!((1..=16).contains(&width), "width should be in 1..=16");
+        // same bounds as `from_bytes`, so every table survives serialization
+        assert!((1..=curve.scalar_bits()).contains(&bits), "bits should be in 1..=scalar_bits");
+        let windows = bits.div_ceil(width as u64);
+
+        let mut rows = Vec::with_capacity(windows as usize);
+        let mut row_base = JacobianPoint::from(base);
+        for _ in 0..windows {
+            let mut row = Vec::with_capacity((1 << width) - 1);
+            let mut acc = row_base.clone();
+            for _ in 1..(1 << width) {
+                row.push(acc.to_point().to_affine());
+                acc = curve.jacobian_add(acc, row_base.clone());
+            }
+            // `2^w * row_base`
+            row_base = acc;
+            rows.push(row);
+        }
+
+        Self {
+            width,
+            bits,
+            rows,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    // `[k]P` with one complete addition per window. Every entry of a row is scanned and the one
+    // for the digit is picked with a masked swap, digit zero keeps the point of infinity.
+    // Scalars wider than the table are reduced modulo the order of the curve,
+    // base is expected to be in the prime subgroup
+    pub fn mul(
+        &self,
+        curve: &WeierstrassCurve<'a>,
+        k: &BigUint,
+    ) -> Result<Point<'a>, Error> {
+        let reduced;
+        let k = match curve.order() {
+            Some(n) if k.bits() > self.bits => {
+                reduced = k % n;
+                &reduced
+            }
+            _ => k,
+        };
+        if k.bits() > self.bits {
+            return Err(Error::InvalidScalar);
+        }
+        let field = curve.a().field;
+        let mut r = Point::infinity(field);
+        for (i, row) in self.rows.iter().enumerate() {
+            let mut digit = 0;
+            for j in (0..self.width).rev() {
+                digit = (digit << 1) | k.bit((i * self.width + j) as u64) as usize;
+            }
+            let mut entry = Point::infinity(field);
+            for (j, p) in row.iter().enumerate() {
+                (entry, _) = Point::conditional_swap(j + 1 == digit, entry, p.clone().into());
+            }
+            r = curve.complete_add(r, entry);
+        }
+        Ok(r)
+    }
+
+    // `width || bits || points`, `width` is one byte and `bits` is 4 big-endian bytes.
+    // Each point is `0x04 || x || y` with big-endian coordinates padded to the byte length
+    // of the field prime, point of infinity is `0x00` followed by zeros of the same length
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.width as u8];
+        let bits = u32::try_from(self.bits).expect("bits are bounded by `with_bits`");
+        bytes.extend_from_slice(&bits.to_be_bytes());
+        for p in self.rows.iter().flatten() {
+            let len = p.x.field.byte_len();
+            if p.is_infinity() {
+                bytes.resize(bytes.len() + 1 + 2 * len, 0);
+                continue;
+            }
+            bytes.push(4);
+            bytes.extend(p.x.to_bytes_be());
+            bytes.extend(p.y.to_bytes_be());
+        }
+        bytes
+    }
+
+    // parses output of `to_bytes` for a table of `base`. Header is checked against the curve
+    // and the length of the input before anything is allocated, every point has to be on the curve.
+    // Each row has to start at `base` or at `2^w` times the start of the previous row
+    // and every next entry has to add the start of its row once more
+    pub fn from_bytes(
+        curve: &WeierstrassCurve<'a>,
+        base: &Point<'a>,
+        bytes: &[u8],
+    ) -> Result<Self, Error> {
+        let field = curve.a().field;
+        let len = field.byte_len();
+        if bytes.len() < 5 {
+            return Err(Error::InvalidEncoding);
+        }
+        let width = bytes[0] as usize;
+        if !(1..=16).contains(&width) {
+            return Err(Error::InvalidEncoding);
+        }
+        let bits = u32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]) as u64;
+        if bits == 0 || bits > curve.scalar_bits() {
+            return Err(Error::InvalidEncoding);
+        }
+        let windows = bits.div_ceil(width as u64) as usize;
+        let entries = (1 << width) - 1;
+        if bytes.len() != 5 + windows * entries * (1 + 2 * len) {
+            return Err(Error::InvalidEncoding);
+        }
+
+        let mut points = bytes[5..].chunks_exact(1 + 2 * len);
+        let mut rows = Vec::with_capacity(windows);
+        for _ in 0..windows {
+            let mut row = Vec::with_capacity(entries);
+            for chunk in points.by_ref().take(entries) {
+                let (tag, coordinates) = chunk.split_first().ok_or(Error::InvalidEncoding)?;
+                let (x, y) = coordinates.split_at(len);
+                match tag {
+                    0 if coordinates.iter().all(|&b| b == 0) => {
+                        row.push(AffinePoint::infinity(field));
+                    }
+                    4 => {
+                        let p = AffinePoint::new(
+                            FieldElement::from_bytes_be(field, x)?,
+                            FieldElement::from_bytes_be(field, y)?,
+                        );
+                        if !curve.contains(&p.clone().into()) {
+                            return Err(Error::NotOnCurve);
+                        }
+                        row.push(p);
+                    }
+                    _ => return Err(Error::InvalidEncoding),
+                }
+            }
+            rows.push(row);
+        }
+
+        let mut expected = base.clone();
+        for row in &rows {
+            let first = Point::from(row[0].clone());
+            if first != expected {
+                return Err(Error::InvalidEncoding);
+            }
+            let mut acc = first.clone();
+            for p in &row[1..] {
+                acc = curve.complete_add(acc, first.clone());
+                if Point::from(p.clone()) != acc {
+                    return Err(Error::InvalidEncoding);
+                }
+            }
+            expected = curve.complete_add(acc, first);
+        }
+
+        Ok(Self {
+            width,
+            bits,
+            rows,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use crate::curves::fixed_base::FixedBaseTable;
+    use crate::curves::named::P256;
+    use crate::curves::point::Point;
+    use crate::curves::weierstass::WeierstrassCurve;
+    use crate::error::Error;
+    use crate::field::field::Field;
+
+    #[test]
+    fn small_curve() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one());
+        let base = e.get_base();
+
+        for width in 1..=4 {
+            let table = FixedBaseTable::new(&e, base.clone(), width);
+            let mut expected = Point::infinity(&field);
+            for k in 0_u8..100 {
+                assert_eq!(table.mul(&e, &BigUint::from(k)), Ok(expected.clone()));
+                expected = e.point_add(expected, base.clone());
+            }
+        }
+    }
+
+    #[test]
+    fn generator() {
+        let field = P256.field();
+        let e = P256.curve(&field);
+        let table = FixedBaseTable::new(&e, e.get_base(), 4);
+        assert_eq!(table.bits(), 256);
+
+        let k = BigUint::parse_bytes(b"41FFC1FFFFFE01FFFC0003FFFE0007C001FFF00003FFF07FFE0007C000000003", 16).unwrap();
+        assert_eq!(table.mul(&e, &k), Ok(e.wnaf_mul(k.clone(), e.get_base())));
+        assert_eq!(table.mul(&e, &(P256.order() - BigUint::from(1_u8))), Ok(-e.get_base()));
+        // wider than the table, reduced modulo the order
+        let wide = k.clone() + P256.order() * BigUint::from(5_u8);
+        assert!(wide.bits() > table.bits());
+        assert_eq!(table.mul(&e, &wide), table.mul(&e, &k));
+    }
+
+    #[test]
+    fn wide_scalar_without_order() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one());
+        let table = FixedBaseTable::new(&e, e.get_base(), 2);
+        assert_eq!(table.mul(&e, &BigUint::from(200_u8)), Err(Error::InvalidScalar));
+    }
+
+    #[test]
+    fn serialization() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one());
+        let base = e.get_base();
+        let table = FixedBaseTable::new(&e, base.clone(), 3);
+
+        let bytes = table.to_bytes();
+        // 7-bit scalars for `p = 61` in 3 windows of 7 points
+        assert_eq!(&bytes[..5], &[3, 0, 0, 0, 7]);
+        assert_eq!(bytes.len(), 5 + 3 * 7 * 3);
+        assert_eq!(FixedBaseTable::from_bytes(&e, &base, &bytes), Ok(table.clone()));
+
+        assert_eq!(FixedBaseTable::from_bytes(&e, &base, &bytes[..bytes.len() - 1]), Err(Error::InvalidEncoding));
+        assert_eq!(FixedBaseTable::from_bytes(&e, &base, &[bytes.clone(), vec![0]].concat()), Err(Error::InvalidEncoding));
+
+        let mut corrupted = bytes.clone();
+        corrupted[7] ^= 1;
+        assert_eq!(FixedBaseTable::from_bytes(&e, &base, &corrupted), Err(Error::NotOnCurve));
+        corrupted[7] = 61;
+        assert_eq!(FixedBaseTable::from_bytes(&e, &base, &corrupted), Err(Error::InvalidEncoding));
+
+        // points on the curve but not the multiples they claim to be
+        // tag and two one-byte coordinates
+        let entry = 3;
+        let mut swapped = bytes.clone();
+        swapped[5 + entry..5 + 3 * entry].rotate_left(entry);
+        assert_eq!(FixedBaseTable::from_bytes(&e, &base, &swapped), Err(Error::InvalidEncoding));
+        let mut rows = bytes.clone();
+        rows[5 + 7 * entry..5 + 8 * entry].copy_from_slice(&bytes[5 + entry..5 + 2 * entry]);
+        assert_eq!(FixedBaseTable::from_bytes(&e, &base, &rows), Err(Error::InvalidEncoding));
+
+        // table of a different base
+        let other = e.point_add(base.clone(), base.clone());
+        assert_eq!(FixedBaseTable::from_bytes(&e, &other, &bytes), Err(Error::InvalidEncoding));
+
+        // header is rejected before anything is allocated
+        for header in [[0, 0, 0, 0, 7], [17, 0, 0, 0, 7], [3, 0, 0, 0, 0], [3, 0, 0, 0, 8], [16, 0xFF, 0xFF, 0xFF, 0xFF]] {
+            assert_eq!(FixedBaseTable::from_bytes(&e, &base, &header), Err(Error::InvalidEncoding));
+        }
+        assert_eq!(FixedBaseTable::from_bytes(&e, &base, &[16, 0, 0, 0, 7]), Err(Error::InvalidEncoding));
+    }
+}
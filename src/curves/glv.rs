@@ -0,0 +1,215 @@
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{One, Signed, Zero};
+use crate::curves::point::Point;
+use crate::curves::weierstass::WeierstrassCurve;
+use crate::error::Error;
+use crate::field::field::Field;
+use crate::field::field_element::FieldElement;
+
+// Gallant-Lambert-Vanstone decomposition for curves with `a = 0` and `p = 1 mod 3`.
+// `φ(x, y) = (βx, y)` costs one multiplication and equals `[λ]P` on the prime subgroup,
+// so `[k]P = [k1]P + [k2]φ(P)` with `k1, k2` about half the size of `k`
+// and the doublings are shared by a simultaneous multiplication.
+// https://www.iacr.org/archive/crypto2001/21390189.pdf
+#[derive(Debug, Clone, PartialEq)]
+pub struct Glv<'a> {
+    beta: FieldElement<'a>,
+    lambda: BigUint,
+    order: BigUint,
+    // short basis of `{(x, y) : x + yλ = 0 mod n}`
+    v1: (BigInt, BigInt),
+    v2: (BigInt, BigInt),
+}
+
+impl<'a> Glv<'a> {
+    // picks `λ` that matches `β` used by `WeierstrassCurve::phi`, checked on the standard generator
+    pub fn new(curve: &WeierstrassCurve<'a>) -> Result<Self, Error> {
+        if !curve.a().is_zero() {
+            return Err(Error::NoEndomorphism);
+        }
+        let field = curve.a().field;
+        let order = curve.order().ok_or(Error::UnknownOrder)?.clone();
+        let beta = field.cube_root_of_unity().ok_or(Error::NoEndomorphism)?;
+
+        let scalars = Field::new(order.clone());
+        let lambda = scalars.cube_root_of_unity().ok_or(Error::NoEndomorphism)?;
+        let g = curve.generator().cloned().ok_or(Error::UnknownGenerator)?;
+        if g.is_infinity() {
+            return Err(Error::SmallOrder);
+        }
+        let lambda = [lambda.clone(), lambda.clone() * lambda]
+            .into_iter()
+            .map(|l| l.value)
+            .find(|l| curve.phi(g.clone()) == curve.wnaf_mul(l.clone(), g.clone()))
+            .ok_or(Error::NoEndomorphism)?;
+
+        let (v1, v2) = short_basis(&order, &lambda);
+        Ok(Self {
+            beta,
+            lambda,
+            order,
+            v1,
+            v2,
+        })
+    }
+
+    pub fn beta(&self) -> &FieldElement<'a> {
+        &self.beta
+    }
+
+    pub fn lambda(&self) -> &BigUint {
+        &self.lambda
+    }
+
+    pub fn phi(&self, p: Point<'a>) -> Point<'a> {
+        Point {
+            x: p.x * self.beta.clone(),
+            y: p.y,
+            z: p.z,
+        }
+    }
+
+    // `k = k1 + k2 λ mod n` with `|k1|, |k2| = O(√n)` by rounding `(k, 0)` to the closest lattice vector
+    pub fn decompose(&self, k: &BigUint) -> (BigInt, BigInt) {
+        let k = BigInt::from(k % &self.order);
+        let n = BigInt::from(self.order.clone());
+        let (a1, b1) = &self.v1;
+        let (a2, b2) = &self.v2;
+
+        let c1 = round_div(&(b2 * &k), &n);
+        let c2 = round_div(&(-b1 * &k), &n);
+        let k1 = k - &c1 * a1 - &c2 * a2;
+        let k2 = -c1 * b1 - c2 * b2;
+        (k1, k2)
+    }
+
+    // `[k1]P + [k2]φ(P)` with a single chain of doublings, points in the prime subgroup only
+    pub fn mul(
+        &self,
+        curve: &WeierstrassCurve<'a>,
+        k: &BigUint,
+        p: Point<'a>,
+    ) -> Point<'a> {
+        let (k1, k2) = self.decompose(k);
+        let q = self.phi(p.clone());
+        let signed = |k: BigInt, p: Point<'a>| match k.sign() {
+            Sign::Minus => (k.magnitude().clone(), -p),
+            _ => (k.magnitude().clone(), p),
+        };
+        curve.straus(&[signed(k1, p), signed(k2, q)])
+    }
+}
+
+// extended euclid on `n` and `λ` keeps `s_i n + t_i λ = r_i`, so `(r_i, -t_i)` are lattice vectors.
+// For the last `r_l >= √n` the shortest of them are `(r_l+1, -t_l+1)` and one of `(r_l, -t_l)`, `(r_l+2, -t_l+2)`
+fn short_basis(n: &BigUint, lambda: &BigUint) -> ((BigInt, BigInt), (BigInt, BigInt)) {
+    let n_int = BigInt::from(n.clone());
+    let mut r = (BigInt::from(n.clone()), BigInt::from(lambda.clone()));
+    let mut t = (BigInt::zero(), BigInt::one());
+    let next = |(r0, r1): &(BigInt, BigInt), (t0, t1): &(BigInt, BigInt)| {
+        let q = r0 / r1;
+        ((r1.clone(), r0 - &q * r1), (t1.clone(), t0 - &q * t1))
+    };
+    // advance until `r.1` is the first remainder below `√n`
+    while &r.1 * &r.1 >= n_int {
+        (r, t) = next(&r, &t);
+    }
+    let v1 = (r.1.clone(), -t.1.clone());
+    let prev = (r.0.clone(), -t.0.clone());
+    let (r2, t2) = next(&r, &t);
+    let after = (r2.1, -t2.1);
+
+    let norm = |(a, b): &(BigInt, BigInt)| a * a + b * b;
+    let v2 = if norm(&prev) <= norm(&after) { prev } else { after };
+    (v1, v2)
+}
+
+// `a / b` rounded to the nearest integer, `b > 0`
+fn round_div(a: &BigInt, b: &BigInt) -> BigInt {
+    let half: BigInt = b / 2;
+    if a.is_negative() {
+        -((half - a) / b)
+    } else {
+        (a + half) / b
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigInt, BigUint};
+    use num_traits::Signed;
+    use crate::curves::glv::Glv;
+    use crate::curves::named::{BLS12_381, BN254, P256, SECP256K1};
+    use crate::curves::point::Point;
+    use crate::curves::weierstass::WeierstrassCurve;
+    use crate::error::Error;
+
+    #[test]
+    fn secp256k1() {
+        let field = SECP256K1.field();
+        let e = SECP256K1.curve(&field);
+        let glv = Glv::new(&e).unwrap();
+        let g = e.get_base();
+
+        // https://github.com/bitcoin-core/secp256k1, `β^2` and `λ^2` are the other pair
+        let beta = BigUint::parse_bytes(b"7AE96A2B657C07106E64479EAC3434E99CF0497512F58995C1396C28719501EE", 16).unwrap();
+        let lambda = BigUint::parse_bytes(b"5363AD4CC05C30E0A5261C028812645A122E22EA20816678DF02967C1B23BD72", 16).unwrap();
+        let n = SECP256K1.order();
+        assert!(glv.beta().value == beta || (glv.beta().clone() * glv.beta().clone()).value == beta);
+        assert!(glv.lambda() == &lambda || (glv.lambda() * glv.lambda()) % &n == lambda);
+        assert_eq!(glv.phi(g.clone()), e.wnaf_mul(glv.lambda().clone(), g.clone()));
+        assert_eq!(glv.phi(g.clone()), e.phi(g.clone()));
+
+        let k = BigUint::parse_bytes(b"7FFFFFC03FFFC003FFFFFC007FFF00000000070000100000000E00FFFFFFF3FF", 16).unwrap();
+        let (k1, k2) = glv.decompose(&k);
+        assert!(k1.bits() <= 129 && k2.bits() <= 129);
+        let recomposed = (k1 + k2 * BigInt::from(glv.lambda().clone())) % BigInt::from(n.clone());
+        let recomposed = if recomposed.is_negative() { recomposed + BigInt::from(n.clone()) } else { recomposed };
+        assert_eq!(recomposed, BigInt::from(k.clone()));
+
+        assert_eq!(glv.mul(&e, &k, g.clone()), e.wnaf_mul(k, g.clone()));
+        assert!(glv.mul(&e, &n, g.clone()).is_infinity());
+        assert_eq!(glv.mul(&e, &(n - BigUint::from(1_u8)), g.clone()), -g);
+    }
+
+    #[test]
+    fn bn254() {
+        let field = BN254.field();
+        let e = BN254.curve(&field);
+        let glv = Glv::new(&e).unwrap();
+        let g = e.get_base();
+        assert_eq!(glv.phi(g.clone()), e.wnaf_mul(glv.lambda().clone(), g.clone()));
+
+        let mut k = BigUint::from(1_u8);
+        for _ in 0..8 {
+            k = (k * BigUint::from(0xDEADBEEF_u32) + BigUint::from(12345_u16)) % BN254.order();
+            let (k1, k2) = glv.decompose(&k);
+            assert!(k1.bits() <= 128 && k2.bits() <= 128);
+            assert_eq!(glv.mul(&e, &k, g.clone()), e.wnaf_mul(k.clone(), g.clone()));
+        }
+    }
+
+    #[test]
+    fn bls12_381() {
+        let field = BLS12_381.field();
+        let e = BLS12_381.curve(&field);
+        let glv = Glv::new(&e).unwrap();
+        let g = e.get_base();
+        let k = BLS12_381.order() - BigUint::from(12345_u16);
+        assert_eq!(glv.mul(&e, &k, g.clone()), e.wnaf_mul(k, g));
+    }
+
+    #[test]
+    fn unsupported() {
+        let field = P256.field();
+        assert_eq!(Glv::new(&P256.curve(&field)).err(), Some(Error::NoEndomorphism));
+
+        let field = SECP256K1.field();
+        let e = SECP256K1.curve(&field);
+        let bare = WeierstrassCurve::new(e.a().clone(), e.b().clone())
+            .with_order(SECP256K1.order(), BigUint::from(1_u8));
+        assert_eq!(Glv::new(&bare).err(), Some(Error::UnknownGenerator));
+        let identity = bare.with_generator(Point::infinity(&field));
+        assert_eq!(Glv::new(&identity).err(), Some(Error::SmallOrder));
+    }
+}
@@ -0,0 +1,583 @@
+use num_bigint::BigUint;
+use crate::curves::montgomery::MontgomeryCurve;
+use crate::curves::named::{curve25519, BLS12_381, P256, SECP256K1};
+use crate::curves::point::Point;
+use crate::curves::weierstass::WeierstrassCurve;
+use crate::error::Error;
+use crate::field::field::Field;
+use crate::field::field_element::FieldElement;
+use crate::field::hash_to_field::{hash_to_field, Expander};
+use crate::utils::s_tonelli::tonelli_shanks;
+
+// https://www.rfc-editor.org/rfc/rfc9380.html
+
+// sign of an element of a prime field, section 4.1
+pub fn sgn0(x: &FieldElement) -> bool {
+    x.value.bit(0)
+}
+
+// `1/x` or `0` for `x = 0`
+fn inv0<'a>(x: FieldElement<'a>) -> FieldElement<'a> {
+    let exp = x.field.order.clone() - BigUint::from(2_u8);
+    x ^ exp
+}
+
+fn sqrt<'a>(x: FieldElement<'a>) -> FieldElement<'a> {
+    tonelli_shanks(x).expect("square root of a square").0
+}
+
+impl<'a> WeierstrassCurve<'a> {
+    // simplified Shallue-van de Woestijne-Ulas method, section 6.6.2.
+    // Needs `ab != 0` and a non-square `z` such that `z != -1` and `g(b / za)` is a square,
+    // private since only the suites below call it and their parameters satisfy both
+    fn map_to_curve_sswu(
+        &self,
+        z: &FieldElement<'a>,
+        u: FieldElement<'a>,
+    ) -> Point<'a> {
+        let (a, b) = (self.a().clone(), self.b().clone());
+        assert!(!a.is_zero() && !b.is_zero(), "simplified SWU needs `ab != 0`");
+        let field = a.field;
+        let g = |x: FieldElement<'a>| x.clone() * x.clone() * x.clone() + a.clone() * x + b.clone();
+
+        let zu2 = z.clone() * u.clone() * u.clone();
+        let tv1 = inv0(zu2.clone() * zu2.clone() + zu2.clone());
+        let x1 = if tv1.is_zero() {
+            b.clone() / (z.clone() * a.clone())
+        } else {
+            -b.clone() / a.clone() * (field.one() + tv1)
+        };
+        let gx1 = g(x1.clone());
+        let (x, mut y) = if gx1.is_square() {
+            (x1, sqrt(gx1))
+        } else {
+            let x2 = zu2 * x1;
+            (x2.clone(), sqrt(g(x2)))
+        };
+        if sgn0(&u) != sgn0(&y) {
+            y = -y;
+        }
+        Point {
+            x,
+            y,
+            z: field.one(),
+        }
+    }
+}
+
+impl<'a> MontgomeryCurve<'a> {
+    // Elligator 2, section 6.7.1, `z` is a non-square
+    pub fn map_to_curve_elligator2(
+        &self,
+        z: &FieldElement<'a>,
+        u: FieldElement<'a>,
+    ) -> Point<'a> {
+        let field = self.a().field;
+        let j = self.a().clone() / self.b().clone();
+        let kk = self.b().clone() * self.b().clone();
+        let g = |x: FieldElement<'a>| x.clone() * x.clone() * x.clone() + j.clone() * x.clone() * x.clone() + x / kk.clone();
+
+        let mut x1 = -j.clone() * inv0(field.one() + z.clone() * u.clone() * u);
+        if x1.is_zero() {
+            x1 = -j.clone();
+        }
+        let gx1 = g(x1.clone());
+        // `y` is odd for `x1` and even for `x2`
+        let (x, mut y, odd) = if gx1.is_square() {
+            (x1, sqrt(gx1), true)
+        } else {
+            let x2 = -x1 - j.clone();
+            (x2.clone(), sqrt(g(x2)), false)
+        };
+        if sgn0(&y) != odd {
+            y = -y;
+        }
+        Point {
+            x: x * self.b().clone(),
+            y: y * self.b().clone(),
+            z: field.one(),
+        }
+    }
+}
+
+// rational map `(x, y) -> (x_num(x) / x_den(x), y * y_num(x) / y_den(x))` between curves,
+// polynomials are given by coefficients from the constant term up
+#[derive(Debug, Clone, PartialEq)]
+pub struct Isogeny<'a> {
+    pub x_num: Vec<FieldElement<'a>>,
+    pub x_den: Vec<FieldElement<'a>>,
+    pub y_num: Vec<FieldElement<'a>>,
+    pub y_den: Vec<FieldElement<'a>>,
+}
+
+impl<'a> Isogeny<'a> {
+    fn from_hex(field: &'a Field, [x_num, x_den, y_num, y_den]: [&[&str]; 4]) -> Self {
+        let parse = |c: &[&str]| c.iter()
+            .map(|s| FieldElement::from_hex(field, s).unwrap())
+            .collect();
+        Self {
+            x_num: parse(x_num),
+            x_den: parse(x_den),
+            y_num: parse(y_num),
+            y_den: parse(y_den),
+        }
+    }
+
+    // poles of the map are sent to the point of infinity
+    pub fn map(&self, p: Point<'a>) -> Point<'a> {
+        if p.is_infinity() {
+            return p;
+        }
+        let field = p.x.field;
+        let p = p.to_affine();
+        let eval = |c: &[FieldElement<'a>]| c.iter()
+            .rev()
+            .fold(field.zero(), |acc, c| acc * p.x.clone() + c.clone());
+        let (x_den, y_den) = (eval(&self.x_den), eval(&self.y_den));
+        if x_den.is_zero() || y_den.is_zero() {
+            return Point::infinity(field);
+        }
+        Point {
+            x: eval(&self.x_num) / x_den,
+            y: p.y.clone() * eval(&self.y_num) / y_den,
+            z: field.one(),
+        }
+    }
+}
+
+// built once per suite, so the size of the variants does not matter
+#[allow(clippy::large_enum_variant)]
+enum Map<'a> {
+    Sswu {
+        curve: WeierstrassCurve<'a>,
+        z: FieldElement<'a>,
+    },
+    // SSWU on `iso_curve` with `ab != 0`, mapped to `curve` where `ab = 0`
+    IsogenousSswu {
+        curve: WeierstrassCurve<'a>,
+        iso_curve: WeierstrassCurve<'a>,
+        isogeny: Isogeny<'a>,
+        z: FieldElement<'a>,
+    },
+    Elligator2 {
+        curve: MontgomeryCurve<'a>,
+        z: FieldElement<'a>,
+    },
+}
+
+// hash-to-curve suite, section 8. `encode_to_curve` is the nonuniform `_NU_` variant,
+// `hash_to_curve` the random oracle `_RO_` one
+pub struct HashToCurve<'a> {
+    map: Map<'a>,
+    // clears the cofactor, may differ from it when a multiple is cheaper
+    h_eff: BigUint,
+    expander: Expander,
+    // security level in bits
+    k: usize,
+}
+
+impl<'a> HashToCurve<'a> {
+    // secp256k1_XMD:SHA-256_SSWU_, section 8.7, `field` is `SECP256K1.field()`
+    pub fn secp256k1(field: &'a Field) -> Self {
+        let iso_curve = WeierstrassCurve::new(
+            FieldElement::from_hex(field, "3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533").unwrap(),
+            field.get(BigUint::from(1771_u16)),
+        );
+        Self {
+            map: Map::IsogenousSswu {
+                curve: SECP256K1.curve(field),
+                iso_curve,
+                isogeny: Isogeny::from_hex(field, SECP256K1_ISOGENY),
+                z: -field.get(BigUint::from(11_u8)),
+            },
+            h_eff: BigUint::from(1_u8),
+            expander: Expander::Sha256,
+            k: 128,
+        }
+    }
+
+    // P256_XMD:SHA-256_SSWU_, section 8.2
+    pub fn p256(field: &'a Field) -> Self {
+        Self {
+            map: Map::Sswu {
+                curve: P256.curve(field),
+                z: -field.get(BigUint::from(10_u8)),
+            },
+            h_eff: BigUint::from(1_u8),
+            expander: Expander::Sha256,
+            k: 128,
+        }
+    }
+
+    // BLS12381G1_XMD:SHA-256_SSWU_, section 8.8.1
+    pub fn bls12_381_g1(field: &'a Field) -> Self {
+        let iso_curve = WeierstrassCurve::new(
+            FieldElement::from_hex(field, "144698a3b8e9433d693a02c96d4982b0ea985383ee66a8d8e8981aefd881ac98936f8da0e0f97f5cf428082d584c1d").unwrap(),
+            FieldElement::from_hex(field, "12e2908d11688030018b12e8753eee3b2016c1f0f24f4070a0b9c14fcef35ef55a23215a316ceaa5d1cc48e98e172be0").unwrap(),
+        );
+        Self {
+            map: Map::IsogenousSswu {
+                curve: BLS12_381.curve(field),
+                iso_curve,
+                isogeny: Isogeny::from_hex(field, BLS12_381_ISOGENY),
+                z: field.get(BigUint::from(11_u8)),
+            },
+            h_eff: BigUint::from(0xd201000000010001_u64),
+            expander: Expander::Sha256,
+            k: 128,
+        }
+    }
+
+    // curve25519_XMD:SHA-512_ELL2_, section 8.5, points are in Montgomery coordinates
+    pub fn curve25519(field: &'a Field) -> Self {
+        Self {
+            map: Map::Elligator2 {
+                curve: curve25519(field),
+                z: field.get(BigUint::from(2_u8)),
+            },
+            h_eff: BigUint::from(8_u8),
+            expander: Expander::Sha512,
+            k: 128,
+        }
+    }
+
+    pub fn map_to_curve(&self, u: FieldElement<'a>) -> Point<'a> {
+        match &self.map {
+            Map::Sswu { curve, z } => curve.map_to_curve_sswu(z, u),
+            Map::IsogenousSswu { iso_curve, isogeny, z, .. } => isogeny.map(iso_curve.map_to_curve_sswu(z, u)),
+            Map::Elligator2 { curve, z } => curve.map_to_curve_elligator2(z, u),
+        }
+    }
+
+    pub fn clear_cofactor(&self, p: Point<'a>) -> Point<'a> {
+        match &self.map {
+            Map::Sswu { curve, .. } | Map::IsogenousSswu { curve, .. } => curve.wnaf_mul(self.h_eff.clone(), p),
+            Map::Elligator2 { curve, .. } => {
+                let field = curve.a().field;
+                (0..self.h_eff.bits()).rev().fold(Point::infinity(field), |acc, i| {
+                    let acc = curve.point_double(acc);
+                    if self.h_eff.bit(i) { curve.point_add(acc, p.clone()) } else { acc }
+                })
+            }
+        }
+    }
+
+    // one field element, the output distribution is not uniform
+    pub fn encode_to_curve(&self, msg: &[u8], dst: &[u8]) -> Result<Point<'a>, Error> {
+        let u = hash_to_field(self.field(), msg, dst, 1, self.expander, self.k)?;
+        let [u] = <[FieldElement<'a>; 1]>::try_from(u).unwrap();
+        Ok(self.clear_cofactor(self.map_to_curve(u)))
+    }
+
+    // sum of two mapped elements, indistinguishable from a random oracle
+    pub fn hash_to_curve(&self, msg: &[u8], dst: &[u8]) -> Result<Point<'a>, Error> {
+        let u = hash_to_field(self.field(), msg, dst, 2, self.expander, self.k)?;
+        let [u0, u1] = <[FieldElement<'a>; 2]>::try_from(u).unwrap();
+        let (q0, q1) = (self.map_to_curve(u0), self.map_to_curve(u1));
+        let q = match &self.map {
+            Map::Sswu { curve, .. } | Map::IsogenousSswu { curve, .. } => curve.point_add(q0, q1),
+            Map::Elligator2 { curve, .. } => curve.point_add(q0, q1),
+        };
+        Ok(self.clear_cofactor(q))
+    }
+
+    fn field(&self) -> &'a Field {
+        match &self.map {
+            Map::Sswu { z, .. } | Map::IsogenousSswu { z, .. } | Map::Elligator2 { z, .. } => z.field,
+        }
+    }
+}
+
+// coefficients of the 3-isogeny, appendix E.1
+const SECP256K1_ISOGENY: [&[&str]; 4] = [
+    // x_num
+    &[
+        "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa8c7",
+        "7d3d4c80bc321d5b9f315cea7fd44c5d595d2fc0bf63b92dfff1044f17c6581",
+        "534c328d23f234e6e2a413deca25caece4506144037c40314ecbd0b53d9dd262",
+        "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa88c",
+    ],
+    // x_den
+    &[
+        "d35771193d94918a9ca34ccbb7b640dd86cd409542f8487d9fe6b745781eb49b",
+        "edadc6f64383dc1df7c4b2d51b54225406d36b641f5e41bbc52a56612a8c6d14",
+        "1",
+    ],
+    // y_num
+    &[
+        "4bda12f684bda12f684bda12f684bda12f684bda12f684bda12f684b8e38e23c",
+        "c75e0c32d5cb7c0fa9d0a54b12a0a6d5647ab046d686da6fdffc90fc201d71a3",
+        "29a6194691f91a73715209ef6512e576722830a201be2018a765e85a9ecee931",
+        "2f684bda12f684bda12f684bda12f684bda12f684bda12f684bda12f38e38d84",
+    ],
+    // y_den
+    &[
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffff93b",
+        "7a06534bb8bdb49fd5e9e6632722c2989467c1bfc8e8d978dfb425d2685c2573",
+        "6484aa716545ca2cf3a70c3fa8fe337e0a3d21162f0d6299a7bf8192bfd2a76f",
+        "1",
+    ],
+];
+
+// coefficients of the 11-isogeny, appendix E.2
+const BLS12_381_ISOGENY: [&[&str]; 4] = [
+    // x_num
+    &[
+        "11a05f2b1e833340b809101dd99815856b303e88a2d7005ff2627b56cdb4e2c85610c2d5f2e62d6eaeac1662734649b7",
+        "17294ed3e943ab2f0588bab22147a81c7c17e75b2f6a8417f565e33c70d1e86b4838f2a6f318c356e834eef1b3cb83bb",
+        "d54005db97678ec1d1048c5d10a9a1bce032473295983e56878e501ec68e25c958c3e3d2a09729fe0179f9dac9edcb0",
+        "1778e7166fcc6db74e0609d307e55412d7f5e4656a8dbf25f1b33289f1b330835336e25ce3107193c5b388641d9b6861",
+        "e99726a3199f4436642b4b3e4118e5499db995a1257fb3f086eeb65982fac18985a286f301e77c451154ce9ac8895d9",
+        "1630c3250d7313ff01d1201bf7a74ab5db3cb17dd952799b9ed3ab9097e68f90a0870d2dcae73d19cd13c1c66f652983",
+        "d6ed6553fe44d296a3726c38ae652bfb11586264f0f8ce19008e218f9c86b2a8da25128c1052ecaddd7f225a139ed84",
+        "17b81e7701abdbe2e8743884d1117e53356de5ab275b4db1a682c62ef0f2753339b7c8f8c8f475af9ccb5618e3f0c88e",
+        "80d3cf1f9a78fc47b90b33563be990dc43b756ce79f5574a2c596c928c5d1de4fa295f296b74e956d71986a8497e317",
+        "169b1f8e1bcfa7c42e0c37515d138f22dd2ecb803a0c5c99676314baf4bb1b7fa3190b2edc0327797f241067be390c9e",
+        "10321da079ce07e272d8ec09d2565b0dfa7dccdde6787f96d50af36003b14866f69b771f8c285decca67df3f1605fb7b",
+        "6e08c248e260e70bd1e962381edee3d31d79d7e22c837bc23c0bf1bc24c6b68c24b1b80b64d391fa9c8ba2e8ba2d229",
+    ],
+    // x_den
+    &[
+        "8ca8d548cff19ae18b2e62f4bd3fa6f01d5ef4ba35b48ba9c9588617fc8ac62b558d681be343df8993cf9fa40d21b1c",
+        "12561a5deb559c4348b4711298e536367041e8ca0cf0800c0126c2588c48bf5713daa8846cb026e9e5c8276ec82b3bff",
+        "b2962fe57a3225e8137e629bff2991f6f89416f5a718cd1fca64e00b11aceacd6a3d0967c94fedcfcc239ba5cb83e19",
+        "3425581a58ae2fec83aafef7c40eb545b08243f16b1655154cca8abc28d6fd04976d5243eecf5c4130de8938dc62cd8",
+        "13a8e162022914a80a6f1d5f43e7a07dffdfc759a12062bb8d6b44e833b306da9bd29ba81f35781d539d395b3532a21e",
+        "e7355f8e4e667b955390f7f0506c6e9395735e9ce9cad4d0a43bcef24b8982f7400d24bc4228f11c02df9a29f6304a5",
+        "772caacf16936190f3e0c63e0596721570f5799af53a1894e2e073062aede9cea73b3538f0de06cec2574496ee84a3a",
+        "14a7ac2a9d64a8b230b3f5b074cf01996e7f63c21bca68a81996e1cdf9822c580fa5b9489d11e2d311f7d99bbdcc5a5e",
+        "a10ecf6ada54f825e920b3dafc7a3cce07f8d1d7161366b74100da67f39883503826692abba43704776ec3a79a1d641",
+        "95fc13ab9e92ad4476d6e3eb3a56680f682b4ee96f7d03776df533978f31c1593174e4b4b7865002d6384d168ecdd0a",
+        "1",
+    ],
+    // y_num
+    &[
+        "90d97c81ba24ee0259d1f094980dcfa11ad138e48a869522b52af6c956543d3cd0c7aee9b3ba3c2be9845719707bb33",
+        "134996a104ee5811d51036d776fb46831223e96c254f383d0f906343eb67ad34d6c56711962fa8bfe097e75a2e41c696",
+        "cc786baa966e66f4a384c86a3b49942552e2d658a31ce2c344be4b91400da7d26d521628b00523b8dfe240c72de1f6",
+        "1f86376e8981c217898751ad8746757d42aa7b90eeb791c09e4a3ec03251cf9de405aba9ec61deca6355c77b0e5f4cb",
+        "8cc03fdefe0ff135caf4fe2a21529c4195536fbe3ce50b879833fd221351adc2ee7f8dc099040a841b6daecf2e8fedb",
+        "16603fca40634b6a2211e11db8f0a6a074a7d0d4afadb7bd76505c3d3ad5544e203f6326c95a807299b23ab13633a5f0",
+        "4ab0b9bcfac1bbcb2c977d027796b3ce75bb8ca2be184cb5231413c4d634f3747a87ac2460f415ec961f8855fe9d6f2",
+        "987c8d5333ab86fde9926bd2ca6c674170a05bfe3bdd81ffd038da6c26c842642f64550fedfe935a15e4ca31870fb29",
+        "9fc4018bd96684be88c9e221e4da1bb8f3abd16679dc26c1e8b6e6a1f20cabe69d65201c78607a360370e577bdba587",
+        "e1bba7a1186bdb5223abde7ada14a23c42a0ca7915af6fe06985e7ed1e4d43b9b3f7055dd4eba6f2bafaaebca731c30",
+        "19713e47937cd1be0dfd0b8f1d43fb93cd2fcbcb6caf493fd1183e416389e61031bf3a5cce3fbafce813711ad011c132",
+        "18b46a908f36f6deb918c143fed2edcc523559b8aaf0c2462e6bfe7f911f643249d9cdf41b44d606ce07c8a4d0074d8e",
+        "b182cac101b9399d155096004f53f447aa7b12a3426b08ec02710e807b4633f06c851c1919211f20d4c04f00b971ef8",
+        "245a394ad1eca9b72fc00ae7be315dc757b3b080d4c158013e6632d3c40659cc6cf90ad1c232a6442d9d3f5db980133",
+        "5c129645e44cf1102a159f748c4a3fc5e673d81d7e86568d9ab0f5d396a7ce46ba1049b6579afb7866b1e715475224b",
+        "15e6be4e990f03ce4ea50b3b42df2eb5cb181d8f84965a3957add4fa95af01b2b665027efec01c7704b456be69c8b604",
+    ],
+    // y_den
+    &[
+        "16112c4c3a9c98b252181140fad0eae9601a6de578980be6eec3232b5be72e7a07f3688ef60c206d01479253b03663c1",
+        "1962d75c2381201e1a0cbd6c43c348b885c84ff731c4d59ca4a10356f453e01f78a4260763529e3532f6102c2e49a03d",
+        "58df3306640da276faaae7d6e8eb15778c4855551ae7f310c35a5dd279cd2eca6757cd636f96f891e2538b53dbf67f2",
+        "16b7d288798e5395f20d23bf89edb4d1d115c5dbddbcd30e123da489e726af41727364f2c28297ada8d26d98445f5416",
+        "be0e079545f43e4b00cc912f8228ddcc6d19c9f0f69bbb0542eda0fc9dec916a20b15dc0fd2ededda39142311a5001d",
+        "8d9e5297186db2d9fb266eaac783182b70152c65550d881c5ecd87b6f0f5a6449f38db9dfa9cce202c6477faaf9b7ac",
+        "166007c08a99db2fc3ba8734ace9824b5eecfdfa8d0cf8ef5dd365bc400a0051d5fa9c01a58b1fb93d1a1399126a775c",
+        "16a3ef08be3ea7ea03bcddfabba6ff6ee5a4375efa1f4fd7feb34fd206357132b920f5b00801dee460ee415a15812ed9",
+        "1866c8ed336c61231a1be54fd1d74cc4f9fb0ce4c6af5920abc5750c4bf39b4852cfe2f7bb9248836b233d9d55535d4a",
+        "167a55cda70a6e1cea820597d94a84903216f763e13d87bb5308592e7ea7d4fbc7385ea3d529b35e346ef48bb8913f55",
+        "4d2f259eea405bd48f010a01ad2911d9c6dd039bb61a6290e591b36e636a5c871a5c29f4f83060400f8b49cba8f6aa8",
+        "accbb67481d033ff5852c1e48c50c477f94ff8aefce42d28c0f9a88cea7913516f968986f7ebbea9684b529e2561092",
+        "ad6b9514c767fe3c3613144b45f1496543346d98adf02267d5ceef9a00d9b8693000763e3b90ac11e99b138573345cc",
+        "2660400eb2e4f3b628bdd0d53cd76f2bf565b94e72927c1cb748df27942480e420517bd8714cc80d1fadc1326ed06f7",
+        "e0fa1d816ddc03e6b24255e0d7819c171c40f65e273b853324efcd6356caa205ca2f570f13497804415473a1d634b8f",
+        "1",
+    ],
+];
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use crate::curves::hash_to_curve::HashToCurve;
+    use crate::curves::named::{BLS12_381, P256, SECP256K1};
+    use crate::curves::point::Point;
+    use crate::field::field::Field;
+    use crate::field::field_element::FieldElement;
+    use crate::field::hash_to_field::{hash_to_field, Expander};
+    use crate::utils::s_tonelli::tonelli_shanks;
+
+    fn point<'a>(field: &'a Field, x: &str, y: &str) -> Point<'a> {
+        Point {
+            x: FieldElement::from_hex(field, x).unwrap(),
+            y: FieldElement::from_hex(field, y).unwrap(),
+            z: field.one(),
+        }
+    }
+
+    // appendix J.8.1
+    #[test]
+    fn secp256k1() {
+        let field = SECP256K1.field();
+        let h = HashToCurve::secp256k1(&field);
+        let dst = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+
+        let u = FieldElement::from_hex(&field, "6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3").unwrap();
+        assert_eq!(h.map_to_curve(u), point(
+            &field,
+            "74519ef88b32b425a095e4ebcc84d81b64e9e2c2675340a720bb1a1857b99f1e",
+            "c174fa322ab7c192e11748beed45b508e9fdb1ce046dee9c2cd3a2a86b410936",
+        ));
+        assert_eq!(h.hash_to_curve(b"", dst), Ok(point(
+            &field,
+            "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+            "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+        )));
+        assert_eq!(h.hash_to_curve(b"abc", dst), Ok(point(
+            &field,
+            "3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+            "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6",
+        )));
+    }
+
+    // appendix J.8.2
+    #[test]
+    fn secp256k1_encode() {
+        let field = SECP256K1.field();
+        let h = HashToCurve::secp256k1(&field);
+        let dst = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_NU_";
+
+        assert_eq!(h.encode_to_curve(b"", dst), Ok(point(
+            &field,
+            "a4792346075feae77ac3b30026f99c1441b4ecf666ded19b7522cf65c4c55c5b",
+            "62c59e2a6aeed1b23be5883e833912b08ba06be7f57c0e9cdc663f31639ff3a7",
+        )));
+        assert_eq!(h.encode_to_curve(b"abc", dst), Ok(point(
+            &field,
+            "3f3b5842033fff837d504bb4ce2a372bfeadbdbd84a1d2b678b6e1d7ee426b9d",
+            "902910d1fef15d8ae2006fc84f2a5a7bda0e0407dc913062c3a493c4f5d876a5",
+        )));
+    }
+
+    // appendix J.1.1
+    #[test]
+    fn p256() {
+        let field = P256.field();
+        let h = HashToCurve::p256(&field);
+        let dst = b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_";
+
+        let u = FieldElement::from_hex(&field, "ad5342c66a6dd0ff080df1da0ea1c04b96e0330dd89406465eeba11582515009").unwrap();
+        assert_eq!(h.map_to_curve(u), point(
+            &field,
+            "ab640a12220d3ff283510ff3f4b1953d09fad35795140b1c5d64f313967934d5",
+            "dccb558863804a881d4fff3455716c836cef230e5209594ddd33d85c565b19b1",
+        ));
+        assert_eq!(h.hash_to_curve(b"", dst), Ok(point(
+            &field,
+            "2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4",
+            "8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415",
+        )));
+        assert_eq!(h.hash_to_curve(b"abcdef0123456789", dst), Ok(point(
+            &field,
+            "65038ac8f2b1def042a5df0b33b1f4eca6bff7cb0f9c6c1526811864e544ed80",
+            "cad44d40a656e7aff4002a8de287abc8ae0482b5ae825822bb870d6df9b56ca3",
+        )));
+    }
+
+    // appendix J.1.2
+    #[test]
+    fn p256_encode() {
+        let field = P256.field();
+        let h = HashToCurve::p256(&field);
+        let dst = b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_NU_";
+
+        assert_eq!(h.encode_to_curve(b"", dst), Ok(point(
+            &field,
+            "f871caad25ea3b59c16cf87c1894902f7e7b2c822c3d3f73596c5ace8ddd14d1",
+            "87b9ae23335bee057b99bac1e68588b18b5691af476234b8971bc4f011ddc99b",
+        )));
+        assert_eq!(h.encode_to_curve(b"abc", dst), Ok(point(
+            &field,
+            "fc3f5d734e8dce41ddac49f47dd2b8a57257522a865c124ed02b92b5237befa4",
+            "fe4d197ecf5a62645b9690599e1d80e82c500b22ac705a0b421fac7b47157866",
+        )));
+    }
+
+    // appendix J.9.1 and J.9.2
+    #[test]
+    fn bls12_381_g1() {
+        let field = BLS12_381.field();
+        let h = HashToCurve::bls12_381_g1(&field);
+
+        let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        assert_eq!(h.hash_to_curve(b"", dst), Ok(point(
+            &field,
+            "052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1",
+            "08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265",
+        )));
+
+        let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_NU_";
+        assert_eq!(h.encode_to_curve(b"", dst), Ok(point(
+            &field,
+            "184bb665c37ff561a89ec2122dd343f20e0f4cbcaec84e3c3052ea81d1834e192c426074b02ed3dca4e7676ce4ce48ba",
+            "04407b8d35af4dacc809927071fc0405218f1401a6d15af775810e4e460064bcc9468beeba82fdc751be70476c888bf3",
+        )));
+        let p = h.encode_to_curve(b"abc", dst).unwrap();
+        assert_eq!(p, point(
+            &field,
+            "009769f3ab59bfd551d53a5f846b9984c59b97d6842b20a2c565baa167945e3d026a3755b6345df8ec7e6acb6868ae6d",
+            "1532c00cf61aa3d0ce3e5aa20c3b531a2abd2c770a790a2613818303c6b830ffc0ecf6c357af3317b9575c567f11cd2c",
+        ));
+        assert!(BLS12_381.curve(&field).is_in_prime_subgroup(&p).unwrap());
+    }
+
+    // appendix J.7.1 and J.7.2
+    #[test]
+    fn curve25519() {
+        let field = Field::new((BigUint::from(1_u8) << 255) - BigUint::from(19_u8));
+        let h = HashToCurve::curve25519(&field);
+
+        let dst = b"QUUX-V01-CS02-with-curve25519_XMD:SHA-512_ELL2_RO_";
+        assert_eq!(h.hash_to_curve(b"", dst), Ok(point(
+            &field,
+            "2de3780abb67e861289f5749d16d3e217ffa722192d16bbd9d1bfb9d112b98c0",
+            "3b5dc2a498941a1033d176567d457845637554a2fe7a3507d21abd1c1bd6e878",
+        )));
+        let dst = b"QUUX-V01-CS02-with-curve25519_XMD:SHA-512_ELL2_NU_";
+        assert_eq!(h.encode_to_curve(b"", dst), Ok(point(
+            &field,
+            "1bb913f0c9daefa0b3375378ffa534bda5526c97391952a7789eb976edfe4d08",
+            "4548368f4f983243e747b62a600840ae7c1dab5c723991f85d3a9768479f3ec4",
+        )));
+    }
+
+    // edwards25519 suites of appendix J.5 use the same map and differ in the tag
+    // and in the birational map `(s, t) -> (√-486664 s / t, (s - 1) / (s + 1))` to Edwards25519
+    #[test]
+    fn edwards25519() {
+        let field = Field::new((BigUint::from(1_u8) << 255) - BigUint::from(19_u8));
+        let h = HashToCurve::curve25519(&field);
+        let (c, c_neg) = tonelli_shanks(-field.get(BigUint::from(486664_u32))).unwrap();
+        let c = if c.value.bit(0) { c_neg } else { c };
+        let to_edwards = |p: Point| {
+            let (s, t) = (p.x, p.y);
+            let hex = |x: FieldElement| x.to_hex();
+            (hex(c.clone() * s.clone() / t), hex((s.clone() - field.one()) / (s + field.one())))
+        };
+
+        let dst = b"QUUX-V01-CS02-with-edwards25519_XMD:SHA-512_ELL2_RO_";
+        let u = hash_to_field(&field, b"abc", dst, 2, Expander::Sha512, 128).unwrap();
+        assert_eq!(u[0].to_hex(), "5081955c4141e4e7d02ec0e36becffaa1934df4d7a270f70679c78f9bd57c227");
+        assert_eq!(to_edwards(h.hash_to_curve(b"abc", dst).unwrap()), (
+            "608040b42285cc0d72cbb3985c6b04c935370c7361f4b7fbdb1ae7f8c1a8ecad".to_string(),
+            "1a8395b88338f22e435bbd301183e7f20a5f9de643f11882fb237f88268a5531".to_string(),
+        ));
+
+        let dst = b"QUUX-V01-CS02-with-edwards25519_XMD:SHA-512_ELL2_NU_";
+        assert_eq!(to_edwards(h.encode_to_curve(b"", dst).unwrap()), (
+            "1ff2b70ecf862799e11b7ae744e3489aa058ce805dd323a936375a84695e76da".to_string(),
+            "222e314d04a4d5725e9f2aff9fb2a6b69ef375a1214eb19021ceab2d687f0f9b".to_string(),
+        ));
+    }
+
+    #[test]
+    fn empty_tag() {
+        let field = P256.field();
+        let h = HashToCurve::p256(&field);
+        assert!(h.hash_to_curve(b"abc", b"").is_err());
+    }
+}
@@ -0,0 +1,157 @@
+use std::ops::Neg;
+use num_bigint::BigUint;
+use crate::curves::point::Point;
+use crate::field::field::Field;
+use crate::field::field_element::FieldElement;
+
+// jacobian coordinates `(X:Y:Z)` with `x = X/Z^2` and `y = Y/Z^3`
+#[derive(Debug, Clone, PartialEq)]
+pub struct JacobianPoint<'a> {
+    pub x: FieldElement<'a>,
+    pub y: FieldElement<'a>,
+    pub z: FieldElement<'a>,
+}
+
+// doubling formulas depend on `a`, curve detects which one to use on creation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoefficientA {
+    // secp256k1, BN and BLS curves
+    Zero,
+    // NIST curves
+    MinusThree,
+    Generic,
+}
+
+impl CoefficientA {
+    pub fn detect(a: &FieldElement<'_>) -> Self {
+        if a.is_zero() {
+            CoefficientA::Zero
+        } else if a.clone() + a.field.get(BigUint::from(3_u8)) == a.field.zero() {
+            CoefficientA::MinusThree
+        } else {
+            CoefficientA::Generic
+        }
+    }
+}
+
+impl<'a> JacobianPoint<'a> {
+    pub fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    pub fn infinity(f: &'a Field) -> Self {
+        Self {
+            x: f.one(),
+            y: f.one(),
+            z: f.zero(),
+        }
+    }
+
+    // performs single inversion, point of infinity stays at infinity
+    pub fn to_point(&self) -> Point<'a> {
+        if self.is_infinity() {
+            return Point::infinity(self.x.field);
+        }
+        let z_inv = self.z.clone().inverse();
+        let zz_inv = z_inv.clone() * z_inv.clone();
+        Point {
+            x: self.x.clone() * zz_inv.clone(),
+            y: self.y.clone() * zz_inv * z_inv,
+            z: self.x.field.one(),
+        }
+    }
+}
+
+// homogeneous `(X:Y:Z)` is `(XZ:YZ^2:Z)` in jacobian coordinates, no inversion needed
+impl<'a> From<Point<'a>> for JacobianPoint<'a> {
+    fn from(p: Point<'a>) -> Self {
+        if p.is_infinity() {
+            return Self::infinity(p.x.field);
+        }
+        Self {
+            x: p.x * p.z.clone(),
+            y: p.y * p.z.clone() * p.z.clone(),
+            z: p.z,
+        }
+    }
+}
+
+impl Neg for JacobianPoint<'_> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self {
+            x: self.x,
+            y: -self.y,
+            z: self.z,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use crate::curves::jacobian::{CoefficientA, JacobianPoint};
+    use crate::curves::weierstass::WeierstrassCurve;
+    use crate::field::field::Field;
+    use crate::test_utils::weierstrass_points;
+
+    // same point with `Z` scaled by `λ`
+    fn scale<'a>(p: JacobianPoint<'a>, l: u8) -> JacobianPoint<'a> {
+        let l = p.x.field.get(BigUint::from(l));
+        JacobianPoint {
+            x: p.x * l.clone() * l.clone(),
+            y: p.y * l.clone() * l.clone() * l.clone(),
+            z: p.z * l,
+        }
+    }
+
+    fn check(e: &WeierstrassCurve<'_>) {
+        let points = weierstrass_points(e);
+        for p in &points {
+            let jp = scale(JacobianPoint::from(p.clone()), 3);
+            assert_eq!(e.jacobian_double(jp.clone()).to_point(), e.point_add(p.clone(), p.clone()));
+            for q in &points {
+                let jq = scale(JacobianPoint::from(q.clone()), 5);
+                assert_eq!(
+                    e.jacobian_add(jp.clone(), jq).to_point(),
+                    e.point_add(p.clone(), q.clone()),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn generic() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one());
+        assert_eq!(e.coefficient_a(), CoefficientA::Generic);
+        check(&e);
+    }
+
+    #[test]
+    fn a_zero() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = WeierstrassCurve::new(field.zero(), field.get(BigUint::from(7_u8)));
+        assert_eq!(e.coefficient_a(), CoefficientA::Zero);
+        check(&e);
+    }
+
+    #[test]
+    fn a_minus_three() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = WeierstrassCurve::new(-field.get(BigUint::from(3_u8)), field.get(BigUint::from(5_u8)));
+        assert_eq!(e.coefficient_a(), CoefficientA::MinusThree);
+        check(&e);
+    }
+
+    #[test]
+    fn from_projective() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one());
+        let base = e.get_base();
+
+        // homogeneous `[2]P` has `Z != 1`
+        let p = e.project_point_double(base.clone());
+        assert_eq!(JacobianPoint::from(p).to_point(), e.point_add(base.clone(), base));
+    }
+}
@@ -0,0 +1,376 @@
+use num_bigint::BigUint;
+use crate::curves::point::Point;
+use crate::curves::weierstass::WeierstrassCurve;
+use crate::error::Error;
+use crate::field::field_element::FieldElement;
+
+// y^2 + a1*xy + a3*y = x^3 + a2*x^2 + a4*x + a6
+// unlike short form it's defined over fields of any characteristic, including 2 and 3
+pub struct LongWeierstrassCurve<'a> {
+    a1: FieldElement<'a>,
+    a2: FieldElement<'a>,
+    a3: FieldElement<'a>,
+    a4: FieldElement<'a>,
+    a6: FieldElement<'a>,
+}
+
+// Silverman, "The Arithmetic of Elliptic Curves", III.1
+impl<'a> LongWeierstrassCurve<'a> {
+    pub fn new (
+        a1: FieldElement<'a>,
+        a2: FieldElement<'a>,
+        a3: FieldElement<'a>,
+        a4: FieldElement<'a>,
+        a6: FieldElement<'a>,
+    ) -> Self {
+        assert!(
+            [&a2, &a3, &a4, &a6].iter().all(|c| c.field == a1.field),
+            "should be in the same field",
+        );
+        Self {
+            a1,
+            a2,
+            a3,
+            a4,
+            a6,
+        }
+    }
+
+    // same as `new` but rejects singular curves
+    pub fn try_new (
+        a1: FieldElement<'a>,
+        a2: FieldElement<'a>,
+        a3: FieldElement<'a>,
+        a4: FieldElement<'a>,
+        a6: FieldElement<'a>,
+    ) -> Result<Self, Error> {
+        if [&a2, &a3, &a4, &a6].iter().any(|c| c.field != a1.field) {
+            return Err(Error::FieldMismatch);
+        }
+        let curve = Self {
+            a1,
+            a2,
+            a3,
+            a4,
+            a6,
+        };
+        if curve.discriminant().is_zero() {
+            return Err(Error::SingularCurve);
+        }
+        Ok(curve)
+    }
+
+    // `y^2 = x^3 + Ax + B` is `a1 = a2 = a3 = 0, a4 = A, a6 = B`
+    pub fn from_short (curve: &WeierstrassCurve<'a>) -> Self {
+        let field = curve.a().field;
+        Self {
+            a1: field.zero(),
+            a2: field.zero(),
+            a3: field.zero(),
+            a4: curve.a().clone(),
+            a6: curve.b().clone(),
+        }
+    }
+
+    fn get(&self, v: u8) -> FieldElement<'a> {
+        self.a1.field.get(BigUint::from(v))
+    }
+
+    // b2 = a1^2 + 4a2
+    pub fn b2(&self) -> FieldElement<'a> {
+        self.a1.clone() * self.a1.clone() + self.get(4) * self.a2.clone()
+    }
+
+    // b4 = 2a4 + a1a3
+    pub fn b4(&self) -> FieldElement<'a> {
+        self.get(2) * self.a4.clone() + self.a1.clone() * self.a3.clone()
+    }
+
+    // b6 = a3^2 + 4a6
+    pub fn b6(&self) -> FieldElement<'a> {
+        self.a3.clone() * self.a3.clone() + self.get(4) * self.a6.clone()
+    }
+
+    // b8 = a1^2 a6 + 4a2 a6 - a1 a3 a4 + a2 a3^2 - a4^2
+    pub fn b8(&self) -> FieldElement<'a> {
+        self.a1.clone() * self.a1.clone() * self.a6.clone()
+            + self.get(4) * self.a2.clone() * self.a6.clone()
+            - self.a1.clone() * self.a3.clone() * self.a4.clone()
+            + self.a2.clone() * self.a3.clone() * self.a3.clone()
+            - self.a4.clone() * self.a4.clone()
+    }
+
+    // c4 = b2^2 - 24b4
+    pub fn c4(&self) -> FieldElement<'a> {
+        let b2 = self.b2();
+        b2.clone() * b2 - self.get(24) * self.b4()
+    }
+
+    // c6 = -b2^3 + 36b2 b4 - 216b6
+    pub fn c6(&self) -> FieldElement<'a> {
+        let b2 = self.b2();
+        -(b2.clone() * b2.clone() * b2.clone())
+            + self.get(36) * b2 * self.b4()
+            - self.get(216) * self.b6()
+    }
+
+    // Δ = -b2^2 b8 - 8b4^3 - 27b6^2 + 9b2 b4 b6
+    pub fn discriminant(&self) -> FieldElement<'a> {
+        let (b2, b4, b6, b8) = (self.b2(), self.b4(), self.b6(), self.b8());
+        -(b2.clone() * b2.clone() * b8)
+            - self.get(8) * b4.clone() * b4.clone() * b4.clone()
+            - self.get(27) * b6.clone() * b6.clone()
+            + self.get(9) * b2 * b4 * b6
+    }
+
+    // j = c4^3 / Δ, undefined for singular curves
+    pub fn j_invariant(&self) -> Option<FieldElement<'a>> {
+        let din = self.discriminant();
+        if din.is_zero() {
+            return None;
+        }
+        let c4 = self.c4();
+        Some(c4.clone() * c4.clone() * c4 / din)
+    }
+
+    pub fn contains(&self, p: &Point<'a>) -> bool {
+        if p.is_infinity() {
+            return true;
+        }
+        let (x, y) = (p.x.clone() / p.z.clone(), p.y.clone() / p.z.clone());
+        let lhs = y.clone() * y.clone() + self.a1.clone() * x.clone() * y.clone() + self.a3.clone() * y;
+        let rhs = x.clone() * x.clone() * x.clone()
+            + self.a2.clone() * x.clone() * x.clone()
+            + self.a4.clone() * x
+            + self.a6.clone();
+        lhs == rhs
+    }
+
+    // -(x, y) = (x, -y - a1x - a3)
+    pub fn negate(&self, p: Point<'a>) -> Point<'a> {
+        if p.is_infinity() {
+            return p;
+        }
+        Point {
+            y: -p.y - self.a1.clone() * p.x.clone() - self.a3.clone(),
+            x: p.x,
+            z: p.z,
+        }
+    }
+
+    pub fn point_double(
+        &self,
+        p1: Point<'a>,
+    ) -> Point<'a> {
+        self.point_add(p1.clone(), p1)
+    }
+
+    pub fn point_add(
+        &self,
+        p1: Point<'a>,
+        p2: Point<'a>,
+    ) -> Point<'a> {
+        if p1.is_infinity() {
+            return p2;
+        }
+        if p2.is_infinity() {
+            return p1;
+        }
+
+        // `y = λx + ν` is the line through `P1` and `P2` (or tangent if `P1 = P2`)
+        let (slope, intercept) = if p1.x == p2.x {
+            let din = self.get(2) * p1.y.clone() + self.a1.clone() * p1.x.clone() + self.a3.clone();
+            if p1.y != p2.y || din.is_zero() {
+                // `P2 = -P1`, vertical line
+                return Point::infinity(p1.x.field);
+            }
+            let xx = p1.x.clone() * p1.x.clone();
+            let slope = self.get(3) * xx.clone()
+                + self.get(2) * self.a2.clone() * p1.x.clone()
+                + self.a4.clone()
+                - self.a1.clone() * p1.y.clone();
+            let intercept = -(xx * p1.x.clone())
+                + self.a4.clone() * p1.x.clone()
+                + self.get(2) * self.a6.clone()
+                - self.a3.clone() * p1.y.clone();
+            (slope / din.clone(), intercept / din)
+        } else {
+            let din = p2.x.clone() - p1.x.clone();
+            let slope = (p2.y.clone() - p1.y.clone()) / din.clone();
+            let intercept = (p1.y * p2.x.clone() - p2.y * p1.x.clone()) / din;
+            (slope, intercept)
+        };
+
+        let x = slope.clone() * slope.clone() + self.a1.clone() * slope.clone() - self.a2.clone() - p1.x - p2.x;
+        Point {
+            z: x.field.one(),
+            y: -(slope + self.a1.clone()) * x.clone() - intercept - self.a3.clone(),
+            x,
+        }
+    }
+
+    // isomorphic short form `y^2 = x^3 - 27c4 x - 54c6`, exists only if `char(F) != 2, 3`
+    pub fn to_short(&self) -> Result<WeierstrassCurve<'a>, Error> {
+        self.check_characteristic()?;
+        WeierstrassCurve::try_new(
+            -(self.get(27) * self.c4()),
+            -(self.get(54) * self.c6()),
+        )
+    }
+
+    // `(x, y) -> (36x + 3b2, 108(2y + a1x + a3))`
+    pub fn map_to_short(&self, p: Point<'a>) -> Result<Point<'a>, Error> {
+        self.check_characteristic()?;
+        if p.is_infinity() {
+            return Ok(p);
+        }
+        let (x, y) = (p.x / p.z.clone(), p.y / p.z);
+        Ok(Point {
+            y: self.get(108) * (self.get(2) * y + self.a1.clone() * x.clone() + self.a3.clone()),
+            x: self.get(36) * x + self.get(3) * self.b2(),
+            z: self.a1.field.one(),
+        })
+    }
+
+    // inverse of `map_to_short`, `(x, y) -> ((x - 3b2) / 36, (y / 108 - a1x' - a3) / 2)`
+    pub fn map_from_short(&self, p: Point<'a>) -> Result<Point<'a>, Error> {
+        self.check_characteristic()?;
+        if p.is_infinity() {
+            return Ok(p);
+        }
+        let (x, y) = (p.x / p.z.clone(), p.y / p.z);
+        let x = (x - self.get(3) * self.b2()) / self.get(36);
+        Ok(Point {
+            y: (y / self.get(108) - self.a1.clone() * x.clone() - self.a3.clone()) / self.get(2),
+            x,
+            z: self.a1.field.one(),
+        })
+    }
+
+    fn check_characteristic(&self) -> Result<(), Error> {
+        let p = &self.a1.field.order;
+        if p == &BigUint::from(2_u8) || p == &BigUint::from(3_u8) {
+            return Err(Error::UnsupportedCharacteristic);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use crate::curves::long_weierstrass::LongWeierstrassCurve;
+    use crate::curves::point::Point;
+    use crate::error::Error;
+    use crate::field::field::Field;
+    use crate::field::field_element::FieldElement;
+    use crate::test_utils::affine_points;
+
+    fn curve(field: &Field, a: [u8; 5]) -> LongWeierstrassCurve<'_> {
+        LongWeierstrassCurve::new(
+            field.get(a[0].into()),
+            field.get(a[1].into()),
+            field.get(a[2].into()),
+            field.get(a[3].into()),
+            field.get(a[4].into()),
+        )
+    }
+
+    fn all_points<'a>(field: &'a Field, e: &LongWeierstrassCurve<'a>) -> Vec<Point<'a>> {
+        let affine = |(x, y): (FieldElement<'a>, FieldElement<'a>)| Point { x, y, z: field.one() };
+        let mut points = vec![Point::infinity(field)];
+        points.extend(
+            affine_points(field, |x, y| e.contains(&affine((x.clone(), y.clone()))))
+                .into_iter()
+                .map(affine),
+        );
+        points
+    }
+
+    // every sum stays on the curve, `P + (-P) = O` and addition is associative
+    fn check_group_law<'a>(e: &LongWeierstrassCurve<'a>, points: &[Point<'a>]) {
+        for p in points {
+            assert!(e.point_add(p.clone(), e.negate(p.clone())).is_infinity());
+            for q in points {
+                let pq = e.point_add(p.clone(), q.clone());
+                assert!(e.contains(&pq));
+                assert_eq!(pq, e.point_add(q.clone(), p.clone()));
+                for r in points {
+                    assert_eq!(
+                        e.point_add(pq.clone(), r.clone()),
+                        e.point_add(p.clone(), e.point_add(q.clone(), r.clone())),
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn characteristic_two() {
+        let field = Field::new(BigUint::from(2_u8));
+
+        // y^2 + xy = x^3 + 1
+        let e = curve(&field, [1, 0, 0, 0, 1]);
+        assert_eq!(e.discriminant(), field.one());
+        assert_eq!(e.j_invariant(), Some(field.one()));
+        let points = all_points(&field, &e);
+        assert_eq!(points.len(), 4);
+        check_group_law(&e, &points);
+        assert_eq!(e.to_short().err(), Some(Error::UnsupportedCharacteristic));
+
+        // supersingular y^2 + y = x^3
+        let e = curve(&field, [0, 0, 1, 0, 0]);
+        assert_eq!(e.j_invariant(), Some(field.zero()));
+        let points = all_points(&field, &e);
+        assert_eq!(points.len(), 3);
+        check_group_law(&e, &points);
+
+        // y^2 = x^3 is singular in any characteristic, and so is every short form in characteristic 2
+        assert_eq!(
+            LongWeierstrassCurve::try_new(field.zero(), field.zero(), field.zero(), field.one(), field.one()).err(),
+            Some(Error::SingularCurve),
+        );
+    }
+
+    #[test]
+    fn characteristic_three() {
+        let field = Field::new(BigUint::from(3_u8));
+
+        // y^2 = x^3 + x^2 + 2
+        let e = curve(&field, [0, 1, 0, 0, 2]);
+        assert_eq!(e.discriminant(), field.one());
+        let points = all_points(&field, &e);
+        assert_eq!(points.len(), 3);
+        check_group_law(&e, &points);
+        assert_eq!(e.to_short().err(), Some(Error::UnsupportedCharacteristic));
+    }
+
+    #[test]
+    fn isomorphism_to_short() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = curve(&field, [1, 2, 3, 4, 5]);
+        assert_eq!(e.discriminant(), field.get(BigUint::from(19_u8)));
+
+        let points = all_points(&field, &e);
+        assert_eq!(points.len(), 49);
+
+        let short = e.to_short().unwrap();
+        assert_eq!(short.j_invariant(), e.j_invariant());
+        assert_eq!(short.points_count(), Ok(BigUint::from(49_u8)));
+
+        for p in &points {
+            let mapped = e.map_to_short(p.clone()).unwrap();
+            assert_eq!(&e.map_from_short(mapped.clone()).unwrap(), p);
+            // homomorphism, `φ(P + Q) = φ(P) + φ(Q)`
+            for q in &points {
+                assert_eq!(
+                    e.map_to_short(e.point_add(p.clone(), q.clone())).unwrap(),
+                    short.point_add(mapped.clone(), e.map_to_short(q.clone()).unwrap()),
+                );
+            }
+        }
+
+        let e = LongWeierstrassCurve::from_short(&short);
+        assert_eq!(e.j_invariant(), short.j_invariant());
+    }
+}
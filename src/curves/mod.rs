@@ -1,2 +1,12 @@
+pub mod edwards;
+pub mod fixed_base;
+pub mod glv;
+pub mod hash_to_curve;
+pub mod jacobian;
+pub mod long_weierstrass;
+pub mod montgomery;
+pub mod msm;
+pub mod named;
 pub mod point;
+pub mod sec1;
 pub mod weierstass;
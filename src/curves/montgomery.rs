@@ -0,0 +1,309 @@
+use num_bigint::BigUint;
+use crate::curves::point::Point;
+use crate::curves::weierstass::WeierstrassCurve;
+use crate::error::Error;
+use crate::field::field_element::FieldElement;
+use crate::utils::s_tonelli::tonelli_shanks;
+
+// By^2 = x^3 + Ax^2 + x
+pub struct MontgomeryCurve<'a> {
+    a: FieldElement<'a>,
+    b: FieldElement<'a>,
+}
+
+// https://www.hyperelliptic.org/EFD/g1p/auto-montgom.html
+// https://martin.kleppmann.com/papers/curve25519.pdf
+impl<'a> MontgomeryCurve<'a> {
+    pub fn new (
+        a: FieldElement<'a>,
+        b: FieldElement<'a>,
+    ) -> Self {
+        assert_eq!(a.field, b.field, "should be in the same field");
+        Self {
+            a,
+            b,
+        }
+    }
+
+    // same as `new` but rejects singular curves, `B(A^2 - 4) = 0`
+    pub fn try_new (
+        a: FieldElement<'a>,
+        b: FieldElement<'a>,
+    ) -> Result<Self, Error> {
+        if a.field != b.field {
+            return Err(Error::FieldMismatch);
+        }
+        let four = a.field.get(BigUint::from(4_u8));
+        if (b.clone() * (a.clone() * a.clone() - four)).is_zero() {
+            return Err(Error::SingularCurve);
+        }
+        Ok(Self {
+            a,
+            b,
+        })
+    }
+
+    // Weierstrass curve `y^2 = x^3 + ax + b` with a point `(α, 0)` of order 2
+    // is birationally equivalent to Montgomery curve iff `3α^2 + a` is a square `1/s^2`,
+    // then `A = 3αs` and `B = s`
+    pub fn from_weierstrass (
+        curve: &WeierstrassCurve<'a>,
+        alpha: FieldElement<'a>,
+    ) -> Result<Self, Error> {
+        let field = alpha.field;
+        let three = field.get(BigUint::from(3_u8));
+        if !(alpha.clone() * alpha.clone() * alpha.clone() + curve.a().clone() * alpha.clone() + curve.b().clone()).is_zero() {
+            return Err(Error::NotOnCurve);
+        }
+        let (_, s) = tonelli_shanks(three.clone() * alpha.clone() * alpha.clone() + curve.a().clone())
+            .ok_or(Error::NoSquareRoot)?;
+        let s = s.inverse();
+        Self::try_new(three * alpha * s.clone(), s)
+    }
+
+    pub fn a(&self) -> &FieldElement<'a> {
+        &self.a
+    }
+
+    pub fn b(&self) -> &FieldElement<'a> {
+        &self.b
+    }
+
+    // `y^2 = x^3 + ax + b` where `a = (3 - A^2) / 3B^2` and `b = (2A^3 - 9A) / 27B^3`
+    pub fn to_weierstrass(&self) -> WeierstrassCurve<'a> {
+        let field = self.a.field;
+        let two = field.get(BigUint::from(2_u8));
+        let three = field.get(BigUint::from(3_u8));
+        let nine = field.get(BigUint::from(9_u8));
+        let twenty_seven = field.get(BigUint::from(27_u8));
+
+        let aa = self.a.clone() * self.a.clone();
+        let bb = self.b.clone() * self.b.clone();
+        WeierstrassCurve::new(
+            (three.clone() - aa.clone()) / (three * bb.clone()),
+            (two * aa * self.a.clone() - nine * self.a.clone()) / (twenty_seven * bb * self.b.clone()),
+        )
+    }
+
+    // `(x, y) -> (x/B + A/3B, y/B)`
+    pub fn map_to_weierstrass(&self, p: Point<'a>) -> Point<'a> {
+        if p.is_infinity() {
+            return p;
+        }
+        let three = self.a.field.get(BigUint::from(3_u8));
+        let (x, y) = (p.x / p.z.clone(), p.y / p.z);
+        Point {
+            x: (x + self.a.clone() / three) / self.b.clone(),
+            y: y / self.b.clone(),
+            z: self.a.field.one(),
+        }
+    }
+
+    // `(x, y) -> (Bx - A/3, By)`, inverse of `map_to_weierstrass`
+    pub fn map_from_weierstrass(&self, p: Point<'a>) -> Point<'a> {
+        if p.is_infinity() {
+            return p;
+        }
+        let three = self.a.field.get(BigUint::from(3_u8));
+        let (x, y) = (p.x / p.z.clone(), p.y / p.z);
+        Point {
+            x: self.b.clone() * x - self.a.clone() / three,
+            y: self.b.clone() * y,
+            z: self.a.field.one(),
+        }
+    }
+
+    pub fn point_double(
+        &self,
+        p1: Point<'a>,
+    ) -> Point<'a> {
+        self.point_add(p1.clone(), p1)
+    }
+
+    pub fn point_add(
+        &self,
+        p1: Point<'a>,
+        p2: Point<'a>,
+    ) -> Point<'a> {
+        if p1.is_infinity() {
+            return p2;
+        }
+        if p2.is_infinity() {
+            return p1;
+        }
+        let field = self.a.field;
+        let slope = if p1.x == p2.x {
+            if p1.y != p2.y || p1.y.is_zero() {
+                return Point::infinity(field);
+            }
+            let two = field.get(BigUint::from(2_u8));
+            let three = field.get(BigUint::from(3_u8));
+            (three * p1.x.clone() * p1.x.clone() + two.clone() * self.a.clone() * p1.x.clone() + field.one())
+                / (two * self.b.clone() * p1.y.clone())
+        } else {
+            (p2.y.clone() - p1.y.clone()) / (p2.x.clone() - p1.x.clone())
+        };
+        let x = self.b.clone() * slope.clone() * slope.clone() - self.a.clone() - p1.x.clone() - p2.x;
+        Point {
+            y: slope * (p1.x - x.clone()) - p1.y,
+            x,
+            z: field.one(),
+        }
+    }
+
+    // `(X:Z) -> [2](X:Z)`, dbl-1987-m-3
+    pub fn x_double(
+        &self,
+        x: FieldElement<'a>,
+        z: FieldElement<'a>,
+    ) -> (FieldElement<'a>, FieldElement<'a>) {
+        let four = self.a.field.get(BigUint::from(4_u8));
+        let a24 = (self.a.clone() + self.a.field.get(BigUint::from(2_u8))) / four;
+
+        let a = x.clone() + z.clone();
+        let aa = a.clone() * a;
+        let b = x - z;
+        let bb = b.clone() * b;
+        let c = aa.clone() - bb.clone();
+        (aa * bb.clone(), c.clone() * (bb + a24 * c))
+    }
+
+    // `(X2:Z2) + (X3:Z3)` knowing their difference `(X1:Z1)`, dadd-1987-m-3
+    pub fn x_add(
+        &self,
+        (x2, z2): (FieldElement<'a>, FieldElement<'a>),
+        (x3, z3): (FieldElement<'a>, FieldElement<'a>),
+        (x1, z1): (FieldElement<'a>, FieldElement<'a>),
+    ) -> (FieldElement<'a>, FieldElement<'a>) {
+        let a = x2.clone() + z2.clone();
+        let b = x2 - z2;
+        let c = x3.clone() + z3.clone();
+        let d = x3 - z3;
+        let da = d * a;
+        let cb = c * b;
+        let s = da.clone() + cb.clone();
+        let t = da - cb;
+        (z1 * s.clone() * s, x1 * t.clone() * t)
+    }
+
+    // x-coordinate of `[k]P` given x-coordinate of `P`, scanning `bits` least significant bits of `k`.
+    // Every step does the same operations regardless of the bit, pair of points is swapped arithmetically.
+    // Point of infinity is returned as `0`
+    pub fn ladder(
+        &self,
+        k: &BigUint,
+        bits: u64,
+        u: FieldElement<'a>,
+    ) -> FieldElement<'a> {
+        let field = self.a.field;
+        let x1 = (u.clone(), field.one());
+        let mut r0 = (field.one(), field.zero());
+        let mut r1 = (u, field.one());
+
+        let mut swap = false;
+        for i in (0..bits).rev() {
+            let bit = k.bit(i);
+            swap ^= bit;
+            (r0.0, r1.0) = FieldElement::conditional_swap(swap, r0.0, r1.0);
+            (r0.1, r1.1) = FieldElement::conditional_swap(swap, r0.1, r1.1);
+            swap = bit;
+
+            r1 = self.x_add(r0.clone(), r1, x1.clone());
+            r0 = self.x_double(r0.0, r0.1);
+        }
+        let (x, _) = FieldElement::conditional_swap(swap, r0.0, r1.0);
+        let (z, _) = FieldElement::conditional_swap(swap, r0.1, r1.1);
+
+        // `z^(p-2) = z^-1`, and `0` for point of infinity
+        let exp = field.order.clone() - BigUint::from(2_u8);
+        x * (z ^ exp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use crate::curves::montgomery::MontgomeryCurve;
+    use crate::curves::point::Point;
+    use crate::error::Error;
+    use crate::field::field::Field;
+    use crate::test_utils::affine_points;
+
+    fn all_points<'a>(field: &'a Field, e: &MontgomeryCurve<'a>) -> Vec<Point<'a>> {
+        affine_points(field, |x, y| {
+            e.b().clone() * y.clone() * y.clone() == x.clone() * x.clone() * x.clone() + e.a().clone() * x.clone() * x.clone() + x.clone()
+        })
+            .into_iter()
+            .map(|(x, y)| Point { x, y, z: field.one() })
+            .collect()
+    }
+
+    #[test]
+    fn singular() {
+        let field = Field::new(BigUint::from(61_u8));
+        assert!(MontgomeryCurve::try_new(field.get(BigUint::from(3_u8)), field.one()).is_ok());
+        assert_eq!(
+            MontgomeryCurve::try_new(field.get(BigUint::from(2_u8)), field.one()).err(),
+            Some(Error::SingularCurve),
+        );
+        assert_eq!(
+            MontgomeryCurve::try_new(field.get(BigUint::from(3_u8)), field.zero()).err(),
+            Some(Error::SingularCurve),
+        );
+    }
+
+    #[test]
+    fn to_weierstrass() {
+        let field = Field::new(BigUint::from(61_u8));
+        let m = MontgomeryCurve::new(field.get(BigUint::from(3_u8)), field.get(BigUint::from(5_u8)));
+        let w = m.to_weierstrass();
+
+        let points = all_points(&field, &m);
+        assert_eq!(w.points_count(), Ok(BigUint::from(points.len() + 1)));
+
+        for p in &points {
+            let mapped = m.map_to_weierstrass(p.clone());
+            assert_eq!(
+                mapped.y.clone() * mapped.y.clone(),
+                mapped.x.clone() * mapped.x.clone() * mapped.x.clone() + w.a().clone() * mapped.x.clone() + w.b().clone(),
+            );
+            assert_eq!(&m.map_from_weierstrass(mapped.clone()), p);
+
+            for q in &points {
+                assert_eq!(
+                    m.map_to_weierstrass(m.point_add(p.clone(), q.clone())),
+                    w.point_add(mapped.clone(), m.map_to_weierstrass(q.clone())),
+                );
+            }
+        }
+
+        // `(0, 0)` is a point of order 2 on every Montgomery curve, it maps to `(A/3B, 0)`
+        let alpha = m.map_to_weierstrass(Point { x: field.zero(), y: field.zero(), z: field.one() }).x;
+        let back = MontgomeryCurve::from_weierstrass(&w, alpha).unwrap();
+        // either `(A, B)` or `(-A, -B)` depending on the chosen root, both map to the same curve
+        assert!(back.a() == m.a() || back.a().clone() == -m.a().clone());
+        assert_eq!(back.to_weierstrass().a(), w.a());
+        assert_eq!(back.to_weierstrass().b(), w.b());
+
+        assert_eq!(MontgomeryCurve::from_weierstrass(&w, field.one()).err(), Some(Error::NotOnCurve));
+    }
+
+    #[test]
+    fn ladder() {
+        let field = Field::new(BigUint::from(61_u8));
+        let m = MontgomeryCurve::new(field.get(BigUint::from(3_u8)), field.get(BigUint::from(5_u8)));
+
+        for p in all_points(&field, &m) {
+            let mut naive = Point::infinity(&field);
+            for k in 0_u8..20 {
+                let x = m.ladder(&BigUint::from(k), 5, p.x.clone());
+                if naive.is_infinity() {
+                    assert!(x.is_zero());
+                } else {
+                    assert_eq!(x, naive.x);
+                }
+                naive = m.point_add(naive, p.clone());
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use num_bigint::BigUint;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use crate::curves::jacobian::JacobianPoint;
+use crate::curves::point::Point;
+use crate::curves::weierstass::WeierstrassCurve;
+use crate::error::Error;
+use crate::utils::wnaf::wnaf;
+
+// below this many terms interleaved wNAF does fewer additions than buckets,
+// the crossover for 256-bit scalars is around 256 terms
+const PIPPENGER_THRESHOLD: usize = 256;
+
+// width of wNAF digits for Straus
+const STRAUS_WIDTH: usize = 5;
+
+// `Σ k_i P_i` sharing doublings between all terms instead of `n` separate multiplications
+impl<'a> WeierstrassCurve<'a> {
+    pub fn multi_scalar_mul(
+        &self,
+        terms: &[(BigUint, Point<'a>)],
+    ) -> Point<'a> {
+        if terms.len() < PIPPENGER_THRESHOLD {
+            self.straus(terms)
+        } else {
+            self.pippenger(terms)
+        }
+    }
+
+    pub fn checked_multi_scalar_mul(
+        &self,
+        terms: &[(BigUint, Point<'a>)],
+    ) -> Result<Point<'a>, Error> {
+        for (_, p) in terms {
+            self.validate(p)?;
+        }
+        Ok(self.multi_scalar_mul(terms))
+    }
+
+    // Shamir's trick generalized to `n` points: one pass over wNAF digits of all scalars
+    // with a single chain of doublings and a table of odd multiples per point
+    pub(crate) fn straus(
+        &self,
+        terms: &[(BigUint, Point<'a>)],
+    ) -> Point<'a> {
+        let digits: Vec<Vec<i32>> = terms.iter()
+            .map(|(k, _)| wnaf(k, STRAUS_WIDTH))
+            .collect();
+        let tables: Vec<Vec<JacobianPoint<'a>>> = terms.iter()
+            .map(|(_, p)| self.odd_multiples(p.clone().into(), STRAUS_WIDTH))
+            .collect();
+        let len = digits.iter().map(Vec::len).max().unwrap_or(0);
+
+        let mut r = JacobianPoint::infinity(self.a().field);
+        for i in (0..len).rev() {
+            r = self.jacobian_double(r);
+            for (digits, table) in digits.iter().zip(&tables) {
+                match digits.get(i) {
+                    Some(&d) if d > 0 => r = self.jacobian_add(r, table[(d / 2) as usize].clone()),
+                    Some(&d) if d < 0 => r = self.jacobian_add(r, -table[(-d / 2) as usize].clone()),
+                    _ => {}
+                }
+            }
+        }
+        r.to_point()
+    }
+
+    // bucket method: scalars are split into `c`-bit windows, within a window every point
+    // is added to the bucket of its digit and `Σ d B_d` is computed with running sums,
+    // windows are then combined with `c` doublings each
+    pub(crate) fn pippenger(
+        &self,
+        terms: &[(BigUint, Point<'a>)],
+    ) -> Point<'a> {
+        let field = self.a().field;
+        let c = pippenger_width(terms.len());
+        let bits = terms.iter().map(|(k, _)| k.bits()).max().unwrap_or(0);
+        let windows = bits.div_ceil(c as u64);
+        let points: Vec<JacobianPoint<'a>> = terms.iter()
+            .map(|(_, p)| p.clone().into())
+            .collect();
+
+        let window_sum = |w: u64| -> JacobianPoint<'a> {
+            let mut buckets = vec![JacobianPoint::infinity(field); (1 << c) - 1];
+            for ((k, _), p) in terms.iter().zip(&points) {
+                let mut digit = 0;
+                for j in (0..c as u64).rev() {
+                    digit = (digit << 1) | k.bit(w * c as u64 + j) as usize;
+                }
+                if digit != 0 {
+                    buckets[digit - 1] = self.jacobian_add(buckets[digit - 1].clone(), p.clone());
+                }
+            }
+
+            // `Σ d B_d = B_max + (B_max + B_max-1) + ..`
+            let mut running = JacobianPoint::infinity(field);
+            let mut sum = JacobianPoint::infinity(field);
+            for bucket in buckets.into_iter().rev() {
+                running = self.jacobian_add(running, bucket);
+                sum = self.jacobian_add(sum, running.clone());
+            }
+            sum
+        };
+
+        #[cfg(feature = "rayon")]
+        let sums: Vec<JacobianPoint<'a>> = (0..windows).into_par_iter().map(window_sum).collect();
+        #[cfg(not(feature = "rayon"))]
+        let sums: Vec<JacobianPoint<'a>> = (0..windows).map(window_sum).collect();
+
+        let mut r = JacobianPoint::infinity(field);
+        for sum in sums.into_iter().rev() {
+            for _ in 0..c {
+                r = self.jacobian_double(r);
+            }
+            r = self.jacobian_add(r, sum);
+        }
+        r.to_point()
+    }
+}
+
+// about `log2(n)` bits per window balances `n` bucket additions against `2^(c+1)` running sum additions
+fn pippenger_width(n: usize) -> usize {
+    (usize::BITS - n.leading_zeros()).saturating_sub(2).clamp(2, 16) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use rand::{Rng, SeedableRng};
+    use rand::rngs::StdRng;
+    use crate::curves::named::SECP256K1;
+    use crate::curves::point::Point;
+    use crate::curves::weierstass::WeierstrassCurve;
+    use crate::field::field::Field;
+
+    #[test]
+    fn small_curve() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one());
+        let base = e.get_base();
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for n in [0, 1, 2, 5, 40] {
+            let terms: Vec<(BigUint, Point)> = (0..n)
+                .map(|_| {
+                    let p = e.double_and_add(BigUint::from(rng.gen_range(0_u8..73)), base.clone());
+                    (BigUint::from(rng.gen_range(0_u16..300)), p)
+                })
+                .collect();
+            let expected = terms.iter().fold(Point::infinity(&field), |acc, (k, p)| {
+                e.point_add(acc, e.double_and_add(k.clone(), p.clone()))
+            });
+            assert_eq!(e.straus(&terms), expected);
+            assert_eq!(e.pippenger(&terms), expected);
+            assert_eq!(e.multi_scalar_mul(&terms), expected);
+        }
+    }
+
+    #[test]
+    fn secp256k1() {
+        let field = SECP256K1.field();
+        let e = SECP256K1.curve(&field);
+        let g = e.get_base();
+        let n = SECP256K1.order();
+        let k1 = BigUint::parse_bytes(b"41FFC1FFFFFE01FFFC0003FFFE0007C001FFF00003FFF07FFE0007C000000003", 16).unwrap();
+        let k2 = BigUint::parse_bytes(b"7FFFFFC03FFFC003FFFFFC007FFF00000000070000100000000E00FFFFFFF3FF", 16).unwrap();
+
+        // `k1 G + k2 (2G) + (n - k1 - 2 k2) G = O`
+        let terms = vec![
+            (k1.clone(), g.clone()),
+            (k2.clone(), e.wnaf_mul(BigUint::from(2_u8), g.clone())),
+            ((n.clone() * 3_u8 - k1 - k2 * 2_u8) % n, g),
+        ];
+        assert!(e.straus(&terms).is_infinity());
+        assert!(e.pippenger(&terms).is_infinity());
+    }
+}
@@ -0,0 +1,354 @@
+use num_bigint::BigUint;
+use crate::curves::edwards::{ed25519_order, ed25519_prime, ED25519_COFACTOR};
+use crate::curves::montgomery::MontgomeryCurve;
+use crate::curves::point::Point;
+use crate::curves::weierstass::WeierstrassCurve;
+use crate::field::field::Field;
+
+// domain parameters of a short Weierstrass curve `y^2 = x^3 + ax + b` over `F_p`
+// with generator `G` of prime order `n` and cofactor `h`, numbers are hex encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurveParams {
+    pub name: &'static str,
+    pub p: &'static str,
+    pub a: &'static str,
+    pub b: &'static str,
+    pub gx: &'static str,
+    pub gy: &'static str,
+    pub n: &'static str,
+    pub h: &'static str,
+}
+
+pub(crate) fn hex(s: &str) -> BigUint {
+    BigUint::parse_bytes(s.as_bytes(), 16).unwrap()
+}
+
+impl CurveParams {
+    pub fn prime(&self) -> BigUint {
+        hex(self.p)
+    }
+
+    pub fn order(&self) -> BigUint {
+        hex(self.n)
+    }
+
+    pub fn cofactor(&self) -> BigUint {
+        hex(self.h)
+    }
+
+    pub fn field(&self) -> Field {
+        Field::new(self.prime())
+    }
+
+    // curve with its order, cofactor and generator set, `field` has to be created by `self.field()`
+    pub fn curve<'a>(&self, field: &'a Field) -> WeierstrassCurve<'a> {
+        assert_eq!(field.order, self.prime(), "field should match the curve");
+        WeierstrassCurve::new(field.get(hex(self.a)), field.get(hex(self.b)))
+            .with_order(self.order(), self.cofactor())
+            .with_generator(self.generator(field))
+    }
+
+    pub fn generator<'a>(&self, field: &'a Field) -> Point<'a> {
+        Point {
+            x: field.get(hex(self.gx)),
+            y: field.get(hex(self.gy)),
+            z: field.one(),
+        }
+    }
+}
+
+// https://www.secg.org/sec2-v2.pdf
+pub const SECP256K1: CurveParams = CurveParams {
+    name: "secp256k1",
+    p: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+    a: "0",
+    b: "7",
+    gx: "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+    gy: "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+    n: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+    h: "1",
+};
+
+// https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-186.pdf
+pub const P256: CurveParams = CurveParams {
+    name: "P-256",
+    p: "FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF",
+    a: "FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFC",
+    b: "5AC635D8AA3A93E7B3EBBD55769886BC651D06B0CC53B0F63BCE3C3E27D2604B",
+    gx: "6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296",
+    gy: "4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5",
+    n: "FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551",
+    h: "1",
+};
+
+pub const P384: CurveParams = CurveParams {
+    name: "P-384",
+    p: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFF0000000000000000FFFFFFFF",
+    a: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFFFF0000000000000000FFFFFFFC",
+    b: "B3312FA7E23EE7E4988E056BE3F82D19181D9C6EFE8141120314088F5013875AC656398D8A2ED19D2A85C8EDD3EC2AEF",
+    gx: "AA87CA22BE8B05378EB1C71EF320AD746E1D3B628BA79B9859F741E082542A385502F25DBF55296C3A545E3872760AB7",
+    gy: "3617DE4A96262C6F5D9E98BF9292DC29F8F41DBD289A147CE9DA3113B5F0B8C00A60B1CE1D7E819D7A431D7C90EA0E5F",
+    n: "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFC7634D81F4372DDF581A0DB248B0A77AECEC196ACCC52973",
+    h: "1",
+};
+
+pub const P521: CurveParams = CurveParams {
+    name: "P-521",
+    p: "1FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+    a: "1FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFC",
+    b: "51953EB9618E1C9A1F929A21A0B68540EEA2DA725B99B315F3B8B489918EF109E156193951EC7E937B1652C0BD3BB1BF073573DF883D2C34F1EF451FD46B503F00",
+    gx: "C6858E06B70404E9CD9E3ECB662395B4429C648139053FB521F828AF606B4D3DBAA14B5E77EFE75928FE1DC127A2FFA8DE3348B3C1856A429BF97E7E31C2E5BD66",
+    gy: "11839296A789A3BC0045C8A5FB42C7D1BD998F54449579B446817AFBD17273E662C97EE72995EF42640C550B9013FAD0761353C7086A272C24088BE94769FD16650",
+    n: "1FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFA51868783BF2F966B7FCC0148F709A5D03BB5C9B8899C47AEBB6FB71E91386409",
+    h: "1",
+};
+
+// https://www.rfc-editor.org/rfc/rfc5639
+pub const BRAINPOOL_P256R1: CurveParams = CurveParams {
+    name: "brainpoolP256r1",
+    p: "A9FB57DBA1EEA9BC3E660A909D838D726E3BF623D52620282013481D1F6E5377",
+    a: "7D5A0975FC2C3057EEF67530417AFFE7FB8055C126DC5C6CE94A4B44F330B5D9",
+    b: "26DC5C6CE94A4B44F330B5D9BBD77CBF958416295CF7E1CE6BCCDC18FF8C07B6",
+    gx: "8BD2AEB9CB7E57CB2C4B482FFC81B7AFB9DE27E1E3BD23C23A4453BD9ACE3262",
+    gy: "547EF835C3DAC4FD97F8461A14611DC9C27745132DED8E545C1D54C72F046997",
+    n: "A9FB57DBA1EEA9BC3E660A909D838D718C397AA3B561A6F7901E0E82974856A7",
+    h: "1",
+};
+
+// also known as alt_bn128, the pairing curve of Ethereum precompiles
+pub const BN254: CurveParams = CurveParams {
+    name: "BN254",
+    p: "30644E72E131A029B85045B68181585D97816A916871CA8D3C208C16D87CFD47",
+    a: "0",
+    b: "3",
+    gx: "1",
+    gy: "2",
+    n: "30644E72E131A029B85045B68181585D2833E84879B9709143E1F593F0000001",
+    h: "1",
+};
+
+// G1 of the pairing, `h = (x - 1)^2 / 3` for the curve parameter `x = -0xD201000000010000`
+pub const BLS12_381: CurveParams = CurveParams {
+    name: "BLS12-381",
+    p: "1A0111EA397FE69A4B1BA7B6434BACD764774B84F38512BF6730D2A0F6B0F6241EABFFFEB153FFFFB9FEFFFFFFFFAAAB",
+    a: "0",
+    b: "4",
+    gx: "17F1D3A73197D7942695638C4FA9AC0FC3688C4F9774B905A14E3A3F171BAC586C55E83FF97A1AEFFB3AF00ADB22C6BB",
+    gy: "8B3F481E3AAA0F1A09E30ED741D8AE4FCF5E095D5D00AF600DB18CB2C04B3EDD03CC744A2888AE40CAA232946C5E7E1",
+    n: "73EDA753299D7D483339D80809A1D80553BDA402FFFE5BFEFFFFFFFF00000001",
+    h: "396C8C005555E1568C00AAAB0000AAAB",
+};
+
+// short Weierstrass form of Curve25519, generator is the image of `u = 9`
+pub const WEI25519: CurveParams = CurveParams {
+    name: "Wei25519",
+    p: "7FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFED",
+    a: "2AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA984914A144",
+    b: "7B425ED097B425ED097B425ED097B425ED097B425ED097B4260B5E9C7710C864",
+    gx: "2AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAD245A",
+    gy: "20AE19A1B8A086B4E01EDD2C7748D14C923D4D7E6D7C61B229E9C5A27ECED3D9",
+    n: "1000000000000000000000000000000014DEF9DEA2F79CD65812631A5CF5D3ED",
+    h: "8",
+};
+
+pub const CURVES: [CurveParams; 8] = [
+    SECP256K1,
+    P256,
+    P384,
+    P521,
+    BRAINPOOL_P256R1,
+    BN254,
+    BLS12_381,
+    WEI25519,
+];
+
+pub fn by_name(name: &str) -> Option<CurveParams> {
+    CURVES.iter().find(|c| c.name == name).copied()
+}
+
+// v^2 = u^3 + 486662u^2 + u over `2^255 - 19`, same field, order and cofactor as Ed25519
+pub fn curve25519(field: &Field) -> MontgomeryCurve<'_> {
+    assert_eq!(field.order, ed25519_prime(), "field should be `2^255 - 19`");
+    MontgomeryCurve::new(field.get(BigUint::from(486662_u32)), field.one())
+}
+
+// `(9, v)` from https://www.rfc-editor.org/rfc/rfc7748
+pub fn curve25519_base_point(field: &Field) -> Point<'_> {
+    Point {
+        x: field.get(BigUint::from(9_u8)),
+        y: field.get(BigUint::parse_bytes(b"14781619447589544791020593568409986887264606134616475288964881837755586237401", 10).unwrap()),
+        z: field.one(),
+    }
+}
+
+pub fn curve25519_order() -> BigUint {
+    ed25519_order()
+}
+
+pub const CURVE25519_COFACTOR: u8 = ED25519_COFACTOR;
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use crate::curves::named::*;
+    use crate::utils::factor::is_probable_prime;
+
+    fn point<'a>(field: &'a Field, x: &str, y: &str) -> Point<'a> {
+        Point {
+            x: field.get(hex(x)),
+            y: field.get(hex(y)),
+            z: field.one(),
+        }
+    }
+
+    // parameters are consistent: `p` and `n` are prime, `G` is on the curve and has order `n`,
+    // `#E = nh` lies within the Hasse interval
+    #[test]
+    fn parameters() {
+        for params in CURVES {
+            let field = params.field();
+            let e = params.curve(&field);
+            let g = e.get_base();
+            assert_eq!(&g, e.generator().unwrap(), "{}", params.name);
+
+            assert!(is_probable_prime(&params.prime()), "{}", params.name);
+            assert!(is_probable_prime(&params.order()), "{}", params.name);
+            assert!(e.contains(&g), "{}", params.name);
+            assert_eq!(e.validate(&g), Ok(()), "{}", params.name);
+            let t = e.trace().unwrap();
+            assert!(t.magnitude() * t.magnitude() <= BigUint::from(4_u8) * params.prime(), "{}", params.name);
+
+            // `[n - 1]G = -G`
+            assert_eq!(e.double_and_add(params.order() - BigUint::from(1_u8), g.clone()), -g, "{}", params.name);
+        }
+    }
+
+    #[test]
+    fn by_names() {
+        assert_eq!(by_name("P-256"), Some(P256));
+        assert_eq!(by_name("BLS12-381").map(|c| c.cofactor()), Some(BigUint::parse_bytes(b"76329603384216526031706109802092473003", 10).unwrap()));
+        assert_eq!(by_name("P-257"), None);
+    }
+
+    // `[k]G` for standard curves, NIST and secp256k1 vectors are from
+    // http://point-at-infinity.org/ecc/nisttv and https://chuckbatson.wordpress.com/2014/11/26/secp256k1-test-vectors/
+    #[test]
+    fn known_answers() {
+        let vectors = [
+            (
+                SECP256K1,
+                "18EBBB95EED0E13",
+                "A90CC3D3F3E146DAADFC74CA1372207CB4B725AE708CEF713A98EDD73D99EF29",
+                "5A79D6B289610C68BC3B47F3D72F9788A26A06868B4D8E433E1E2AD76FB7DC76",
+            ),
+            (
+                SECP256K1,
+                "3",
+                "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+                "388F7B0F632DE8140FE337E62A37F3566500A99934C2231B6CB9FD7584B8E672",
+            ),
+            (
+                P256,
+                "3",
+                "5ECBE4D1A6330A44C8F7EF951D4BF165E6C6B721EFADA985FB41661BC6E7FD6C",
+                "8734640C4998FF7E374B06CE1A64A2ECD82AB036384FB83D9A79B127A27D5032",
+            ),
+            (
+                SECP256K1,
+                "AA5E28D6A97A2479A65527F7290311A3624D4CC0FA1578598EE3C2613BF99522",
+                "34F9460F0E4F08393D192B3C5133A6BA099AA0AD9FD54EBCCFACDFA239FF49C6",
+                "0B71EA9BD730FD8923F6D25A7A91E7DD7728A960686CB5A901BB419E0F2CA232",
+            ),
+            (
+                SECP256K1,
+                "7E2B897B8CEBC6361663AD410835639826D590F393D90A9538881735256DFAE3",
+                "D74BF844B0862475103D96A611CF2D898447E288D34B360BC885CB8CE7C00575",
+                "131C670D414C4546B88AC3FF664611B1C38CEB1C21D76369D7A7A0969D61D97D",
+            ),
+            (
+                P256,
+                "18EBBB95EED0E13",
+                "339150844EC15234807FE862A86BE77977DBFB3AE3D96F4C22795513AEAAB82F",
+                "B1C14DDFDC8EC1B2583F51E85A5EB3A155840F2034730E9B5ADA38B674336A21",
+            ),
+            (
+                P256,
+                "41FFC1FFFFFE01FFFC0003FFFE0007C001FFF00003FFF07FFE0007C000000003",
+                "9EACE8F4B071E677C5350B02F2BB2B384AAE89D58AA72CA97A170572E0FB222F",
+                "1BBDAEC2430B09B93F7CB08678636CE12EAAFD58390699B5FD2F6E1188FC2A78",
+            ),
+            (
+                P384,
+                "2",
+                "08D999057BA3D2D969260045C55B97F089025959A6F434D651D207D19FB96E9E4FE0E86EBE0E64F85B96A9C75295DF61",
+                "8E80F1FA5B1B3CEDB7BFE8DFFD6DBA74B275D875BC6CC43E904E505F256AB4255FFD43E94D39E22D61501E700A940E80",
+            ),
+            (
+                P384,
+                "18EBBB95EED0E13",
+                "A499EFE48839BC3ABCD1C5CEDBDD51904F9514DB44F4686DB918983B0C9DC3AEE05A88B72433E9515F91A329F5F4FA60",
+                "3B7CA28EF31F809C2F1BA24AAED847D0F8B406A4B8968542DE139DB5828CA410E615D1182E25B91B1131E230B727D36A",
+            ),
+            (
+                P521,
+                "2",
+                "433C219024277E7E682FCB288148C282747403279B1CCC06352C6E5505D769BE97B3B204DA6EF55507AA104A3A35C5AF41CF2FA364D60FD967F43E3933BA6D783D",
+                "F4BB8CC7F86DB26700A7F3ECEEEED3F0B5C6B5107C4DA97740AB21A29906C42DBBB3E377DE9F251F6B93937FA99A3248F4EAFCBE95EDC0F4F71BE356D661F41B02",
+            ),
+            (
+                BRAINPOOL_P256R1,
+                "2",
+                "743CF1B8B5CD4F2EB55F8AA369593AC436EF044166699E37D51A14C2CE13EA0E",
+                "36ED163337DEBA9C946FE0BB776529DA38DF059F69249406892ADA097EEB7CD4",
+            ),
+            (
+                BN254,
+                "3",
+                "769BF9AC56BEA3FF40232BCB1B6BD159315D84715B8E679F2D355961915ABF0",
+                "2AB799BEE0489429554FDB7C8D086475319E63B40B9C5B57CDF1FF3DD9FE2261",
+            ),
+            (
+                BN254,
+                "2",
+                "30644E72E131A029B85045B68181585D97816A916871CA8D3C208C16D87CFD3",
+                "15ED738C0E0A7C92E7845F96B2AE9C0A68A6A449E3538FC7FF3EBF7A5A18A2C4",
+            ),
+            (
+                BLS12_381,
+                "2",
+                "572CBEA904D67468808C8EB50A9450C9721DB309128012543902D0AC358A62AE28F75BB8F1C7C42C39A8C5529BF0F4E",
+                "166A9D8CABC673A322FDA673779D8E3822BA3ECB8670E461F73BB9021D5FD76A4C56D9D4CD16BD1BBA86881979749D28",
+            ),
+            (
+                WEI25519,
+                "2",
+                "4B7DED7FC31E9C62841FB71327C01BBF39EA0797C8DFB6070758F1478815734C",
+                "13B57E011700E8AE050A00945D2BA2F377659EB28D8D391EBCD70465C72DF563",
+            ),
+        ];
+        for (params, k, x, y) in vectors {
+            let field = params.field();
+            let e = params.curve(&field);
+            assert_eq!(e.double_and_add(hex(k), e.get_base()), point(&field, x, y), "{}", params.name);
+        }
+    }
+
+    #[test]
+    fn curve25519_family() {
+        let field = WEI25519.field();
+        let m = curve25519(&field);
+        let base = curve25519_base_point(&field);
+        assert_eq!(
+            m.b().clone() * base.y.clone() * base.y.clone(),
+            base.x.clone() * base.x.clone() * base.x.clone() + m.a().clone() * base.x.clone() * base.x.clone() + base.x.clone(),
+        );
+
+        let w = m.to_weierstrass();
+        let e = WEI25519.curve(&field);
+        assert_eq!(w.a(), e.a());
+        assert_eq!(w.b(), e.b());
+        assert_eq!(m.map_to_weierstrass(base), e.get_base());
+        assert_eq!(WEI25519.order(), curve25519_order());
+        assert_eq!(WEI25519.cofactor(), BigUint::from(CURVE25519_COFACTOR));
+    }
+}
@@ -1,8 +1,65 @@
+use std::hash::{Hash, Hasher};
 use std::ops::Neg;
 use crate::field::field::Field;
 use crate::field::field_element::FieldElement;
 
+// affine `(x, y)` or point of infinity, for points that are known to be normalized
 #[derive(Debug, Clone, PartialEq)]
+pub struct AffinePoint<'a> {
+    pub x: FieldElement<'a>,
+    pub y: FieldElement<'a>,
+    pub infinity: bool,
+}
+
+impl<'a> AffinePoint<'a> {
+    pub fn new(x: FieldElement<'a>, y: FieldElement<'a>) -> Self {
+        Self {
+            x,
+            y,
+            infinity: false,
+        }
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.infinity
+    }
+
+    pub fn infinity(f: &'a Field) -> Self {
+        Self {
+            x: f.zero(),
+            y: f.zero(),
+            infinity: true,
+        }
+    }
+}
+
+impl<'a> From<AffinePoint<'a>> for Point<'a> {
+    fn from(p: AffinePoint<'a>) -> Self {
+        if p.is_infinity() {
+            return Point::infinity(p.x.field);
+        }
+        Point {
+            z: p.x.field.one(),
+            x: p.x,
+            y: p.y,
+        }
+    }
+}
+
+impl Neg for AffinePoint<'_> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self {
+            x: self.x,
+            y: -self.y,
+            infinity: self.infinity,
+        }
+    }
+}
+
+// homogeneous projective `(X:Y:Z)` with `x = X/Z` and `y = Y/Z`,
+// so `(X:Y:Z)` and `(λX:λY:λZ)` represent the same point and compare equal
+#[derive(Debug, Clone)]
 pub struct Point<'a> {
     pub x: FieldElement<'a>,
     pub y: FieldElement<'a>,
@@ -21,6 +78,59 @@ impl<'a> Point<'a> {
             z: f.zero(),
         }
     }
+
+    // `(X/Z : Y/Z : 1)`, or `(0:1:0)` for point of infinity
+    pub fn canonical(&self) -> Self {
+        self.to_affine().into()
+    }
+
+    // `(X/Z, Y/Z)` with a single inversion
+    pub fn to_affine(&self) -> AffinePoint<'a> {
+        if self.is_infinity() {
+            return AffinePoint::infinity(self.x.field);
+        }
+        let z_inv = self.z.clone().inverse();
+        AffinePoint::new(self.x.clone() * z_inv.clone(), self.y.clone() * z_inv)
+    }
+
+    // returns `(b, a)` if `choice` is set and `(a, b)` otherwise, see `FieldElement::conditional_swap`
+    pub fn conditional_swap(choice: bool, a: Self, b: Self) -> (Self, Self) {
+        let (ax, bx) = FieldElement::conditional_swap(choice, a.x, b.x);
+        let (ay, by) = FieldElement::conditional_swap(choice, a.y, b.y);
+        let (az, bz) = FieldElement::conditional_swap(choice, a.z, b.z);
+        (
+            Self { x: ax, y: ay, z: az },
+            Self { x: bx, y: by, z: bz },
+        )
+    }
+}
+
+// `X1/Z1 = X2/Z2` and `Y1/Z1 = Y2/Z2` without inversions
+impl PartialEq for Point<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.is_infinity(), other.is_infinity()) {
+            (true, true) => true,
+            (false, false) => {
+                self.x.clone() * other.z.clone() == other.x.clone() * self.z.clone()
+                    && self.y.clone() * other.z.clone() == other.y.clone() * self.z.clone()
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Point<'_> {}
+
+// hashes canonical form to stay consistent with equality
+impl Hash for Point<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let p = self.to_affine();
+        p.infinity.hash(state);
+        if !p.infinity {
+            p.x.value.hash(state);
+            p.y.value.hash(state);
+        }
+    }
 }
 
 impl Neg for Point<'_> {
@@ -0,0 +1,162 @@
+use crate::curves::point::Point;
+use crate::curves::weierstass::WeierstrassCurve;
+use crate::error::Error;
+use crate::field::field_element::FieldElement;
+
+// https://www.secg.org/sec1-v2.pdf, section 2.3.3 and 2.3.4
+const INFINITY: u8 = 0x00;
+const COMPRESSED_EVEN: u8 = 0x02;
+const COMPRESSED_ODD: u8 = 0x03;
+const UNCOMPRESSED: u8 = 0x04;
+
+impl<'a> WeierstrassCurve<'a> {
+    // `0x00` for the point of infinity, otherwise `0x04 || x || y` or `0x02/0x03 || x`
+    // with parity of `y` in the prefix, coordinates are big-endian padded to the byte length of `p`
+    pub fn encode_point(
+        &self,
+        p: &Point<'a>,
+        compressed: bool,
+    ) -> Vec<u8> {
+        let p = p.to_affine();
+        if p.is_infinity() {
+            return vec![INFINITY];
+        }
+        let len = self.a().field.byte_len();
+        let mut bytes = Vec::with_capacity(1 + 2 * len);
+        if compressed {
+            bytes.push(if p.y.value.bit(0) { COMPRESSED_ODD } else { COMPRESSED_EVEN });
+            bytes.extend(p.x.to_bytes_be());
+        } else {
+            bytes.push(UNCOMPRESSED);
+            bytes.extend(p.x.to_bytes_be());
+            bytes.extend(p.y.to_bytes_be());
+        }
+        bytes
+    }
+
+    // strict inverse of `encode_point`: exact length, coordinates below `p` and the point on the curve.
+    // Hybrid `0x06/0x07` encodings are rejected
+    pub fn decode_point(
+        &self,
+        bytes: &[u8],
+    ) -> Result<Point<'a>, Error> {
+        let field = self.a().field;
+        let len = self.a().field.byte_len();
+        let (prefix, rest) = bytes.split_first().ok_or(Error::InvalidEncoding)?;
+        let coordinate = |bytes: &[u8]| FieldElement::from_bytes_be(field, bytes);
+
+        match *prefix {
+            INFINITY if rest.is_empty() => Ok(Point::infinity(field)),
+            UNCOMPRESSED if rest.len() == 2 * len => {
+                let p = Point {
+                    x: coordinate(&rest[..len])?,
+                    y: coordinate(&rest[len..])?,
+                    z: field.one(),
+                };
+                if !self.contains(&p) {
+                    return Err(Error::NotOnCurve);
+                }
+                Ok(p)
+            }
+            COMPRESSED_EVEN | COMPRESSED_ODD if rest.len() == len => {
+                let x = coordinate(rest)?;
+                let (y1, y2) = self.evaluate_y(x.clone()).ok_or(Error::NotOnCurve)?;
+                let odd = *prefix == COMPRESSED_ODD;
+                let y = if y1.value.bit(0) == odd { y1 } else { y2 };
+                // `y = 0` has no odd counterpart
+                if y.value.bit(0) != odd {
+                    return Err(Error::InvalidEncoding);
+                }
+                Ok(Point {
+                    x,
+                    y,
+                    z: field.one(),
+                })
+            }
+            _ => Err(Error::InvalidEncoding),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num_bigint::BigUint;
+    use crate::curves::named::{P256, SECP256K1};
+    use crate::curves::point::Point;
+    use crate::curves::weierstass::WeierstrassCurve;
+    use crate::error::Error;
+    use crate::field::field::Field;
+    use crate::test_utils::hex_bytes;
+
+    #[test]
+    fn generators() {
+        let field = SECP256K1.field();
+        let e = SECP256K1.curve(&field);
+        let g = e.get_base();
+        let compressed = hex_bytes("0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798");
+        assert_eq!(e.encode_point(&g, true), compressed);
+        assert_eq!(e.decode_point(&compressed), Ok(g.clone()));
+        let uncompressed = e.encode_point(&g, false);
+        assert_eq!(uncompressed.len(), 65);
+        assert_eq!(e.decode_point(&uncompressed), Ok(g));
+
+        let field = P256.field();
+        let e = P256.curve(&field);
+        let g = e.get_base();
+        let compressed = hex_bytes("036B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296");
+        assert_eq!(e.encode_point(&g, true), compressed);
+        assert_eq!(e.decode_point(&compressed), Ok(g.clone()));
+        // projective coordinates are normalized
+        let g2 = e.project_point_double(g.clone());
+        assert_eq!(e.decode_point(&e.encode_point(&g2, true)), Ok(g2));
+    }
+
+    #[test]
+    fn round_trip() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one());
+        let mut p = Point::infinity(&field);
+        let base = e.get_base();
+        for _ in 0..73 {
+            for compressed in [false, true] {
+                assert_eq!(e.decode_point(&e.encode_point(&p, compressed)), Ok(p.clone()));
+            }
+            p = e.point_add(p, base.clone());
+        }
+        assert_eq!(e.encode_point(&Point::infinity(&field), true), vec![0]);
+    }
+
+    #[test]
+    fn strict() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one());
+        let p = e.get_base();
+
+        let encoded = e.encode_point(&p, false);
+        assert_eq!(e.decode_point(&[]), Err(Error::InvalidEncoding));
+        assert_eq!(e.decode_point(&[0, 0]), Err(Error::InvalidEncoding));
+        assert_eq!(e.decode_point(&encoded[..2]), Err(Error::InvalidEncoding));
+        // hybrid form
+        assert_eq!(e.decode_point(&[&[6 + p.y.value.bit(0) as u8], &encoded[1..]].concat()), Err(Error::InvalidEncoding));
+        // off the curve
+        assert_eq!(e.decode_point(&[4, encoded[1], encoded[2] ^ 1]), Err(Error::NotOnCurve));
+        // `y + p` is congruent but not canonical
+        assert_eq!(e.decode_point(&[4, encoded[1], encoded[2] + 61]), Err(Error::InvalidEncoding));
+        assert_eq!(e.decode_point(&[2, 61]), Err(Error::InvalidEncoding));
+
+        // `x = 0` gives `y^2 = 1` on this curve, while `x = 1` gives `y^2 = 11` which is not a square
+        assert!(e.decode_point(&[2, 0]).is_ok());
+        assert_eq!(e.decode_point(&[2, 1]), Err(Error::NotOnCurve));
+    }
+
+    #[test]
+    fn order_two() {
+        // `(1, 0)` lies on `y^2 = x^3 + x - 2`
+        let field = Field::new(BigUint::from(61_u8));
+        let e = WeierstrassCurve::new(field.one(), -field.get(BigUint::from(2_u8)));
+        let p = Point { x: field.one(), y: field.zero(), z: field.one() };
+        assert_eq!(e.encode_point(&p, true), vec![2, 1]);
+        assert_eq!(e.decode_point(&[2, 1]), Ok(p));
+        assert_eq!(e.decode_point(&[3, 1]), Err(Error::InvalidEncoding));
+    }
+}
@@ -1,17 +1,42 @@
 use std::collections::HashMap;
-use num_bigint::BigUint;
+use num_bigint::{BigInt, BigUint};
 use num_traits::One;
-use rand::{RngCore, thread_rng};
-use crate::curves::point::Point;
+use rand::{CryptoRng, RngCore, thread_rng};
+use crate::curves::jacobian::{CoefficientA, JacobianPoint};
+use crate::curves::point::{AffinePoint, Point};
+use crate::error::Error;
+use crate::field::field::Field;
 use crate::field::field_element::FieldElement;
 use crate::utils::bit_iter::BitIter;
+use crate::utils::factor::factorize;
+use crate::utils::gcd::u_gcd;
 use crate::utils::s_tonelli::tonelli_shanks;
+use crate::utils::wnaf::wnaf;
 
 // y^2 = x^3 + Ax + B
 pub struct WeierstrassCurve<'a> {
     a: FieldElement<'a>,
-    #[allow(unused)]
     b: FieldElement<'a>,
+    // picks specialized jacobian doubling
+    a_kind: CoefficientA,
+    // order of the prime subgroup and its cofactor, when known
+    order: Option<BigUint>,
+    cofactor: Option<BigUint>,
+    // standard base point, `get_base` falls back to an arbitrary point without it
+    generator: Option<Point<'a>>,
+}
+
+// factorization of the twist order, security of the curve against invalid-point attacks
+// that land on the twist is bound by the largest prime factor
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwistSecurity {
+    pub order: BigUint,
+    pub factors: Vec<(BigUint, u32)>,
+    // composite part that could not be factored, `1` if factorization is complete
+    pub unfactored: BigUint,
+    pub largest_prime_factor: BigUint,
+    // cost of pollard's rho in the largest prime order subgroup, `log2(sqrt(l))`
+    pub security_bits: u64,
 }
 
 // https://www.hyperelliptic.org/EFD/g1p/auto-shortw.html
@@ -22,18 +47,224 @@ impl<'a> WeierstrassCurve<'a> {
     ) -> Self {
         assert_eq!(a.field, b.field, "should be in the same field");
         Self {
+            a_kind: CoefficientA::detect(&a),
             a,
             b,
+            order: None,
+            cofactor: None,
+            generator: None,
+        }
+    }
+
+    // same as `new` but rejects singular curves
+    pub fn try_new (
+        a: FieldElement<'a>,
+        b: FieldElement<'a>,
+    ) -> Result<Self, Error> {
+        if a.field != b.field {
+            return Err(Error::FieldMismatch);
+        }
+        let curve = Self {
+            a_kind: CoefficientA::detect(&a),
+            a,
+            b,
+            order: None,
+            cofactor: None,
+            generator: None,
+        };
+        if curve.discriminant().is_zero() {
+            return Err(Error::SingularCurve);
+        }
+        Ok(curve)
+    }
+
+    pub fn a(&self) -> &FieldElement<'a> {
+        &self.a
+    }
+
+    pub fn b(&self) -> &FieldElement<'a> {
+        &self.b
+    }
+
+    pub fn coefficient_a(&self) -> CoefficientA {
+        self.a_kind
+    }
+
+    // sets order of the prime subgroup and its cofactor, `#E = order * cofactor`
+    pub fn with_order(mut self, order: BigUint, cofactor: BigUint) -> Self {
+        self.order = Some(order);
+        self.cofactor = Some(cofactor);
+        self
+    }
+
+    pub fn order(&self) -> Option<&BigUint> {
+        self.order.as_ref()
+    }
+
+    pub fn cofactor(&self) -> Option<&BigUint> {
+        self.cofactor.as_ref()
+    }
+
+    // `Z/nZ` for the order of the prime subgroup, see `Scalar`
+    pub fn scalar_field(&self) -> Result<Field, Error> {
+        self.order.clone().map(Field::new).ok_or(Error::UnknownOrder)
+    }
+
+    pub fn with_generator(mut self, generator: Point<'a>) -> Self {
+        self.generator = Some(generator);
+        self
+    }
+
+    pub fn generator(&self) -> Option<&Point<'a>> {
+        self.generator.as_ref()
+    }
+
+    // number of points `#E(F_p)` including point of infinity. Known order gives `order * cofactor`,
+    // checked against Hasse's bound `|p + 1 - #E| <= 2√p`. Without it the points are counted
+    // one `x` at a time, which is only done for `p < 2^16`, larger fields need `with_order`
+    pub fn points_count(&self) -> Result<BigUint, Error> {
+        let p = &self.a.field.order;
+        let Some((n, h)) = self.order.as_ref().zip(self.cofactor.as_ref()) else {
+            return self.count_points();
+        };
+        let count = n * h;
+        let t = BigInt::from(p + BigUint::one()) - BigInt::from(count.clone());
+        if t.magnitude() * t.magnitude() > BigUint::from(4_u8) * p {
+            return Err(Error::InvalidOrder);
+        }
+        Ok(count)
+    }
+
+    // `1 + Σ (1 + (f(x) / p))` over all `x`, `f(x) = x^3 + ax + b` gives two points when it's a non-zero square
+    fn count_points(&self) -> Result<BigUint, Error> {
+        let field = self.a.field;
+        let p = u16::try_from(&field.order).map_err(|_| Error::UnknownOrder)?;
+        let mut count = BigUint::one();
+        for x in 0..p {
+            let x = field.get(BigUint::from(x));
+            let f = x.clone() * x.clone() * x.clone() + self.a.clone() * x + self.b.clone();
+            if f.is_zero() {
+                count += 1_u8;
+            } else if f.is_square() {
+                count += 2_u8;
+            }
+        }
+        Ok(count)
+    }
+
+    // trace of Frobenius `t = p + 1 - #E`
+    pub fn trace(&self) -> Result<BigInt, Error> {
+        Ok(BigInt::from(self.a.field.order.clone() + BigUint::one()) - BigInt::from(self.points_count()?))
+    }
+
+    // `E^d: y^2 = x^3 + d^2 A x + d^3 B` where `d` is a quadratic non-residue (e.g. `Field::non_residue`).
+    // Every `x` is either on `E` or on `E^d` (or on both if `y = 0`)
+    pub fn quadratic_twist(&self, d: FieldElement<'a>) -> Result<Self, Error> {
+        if d.field != self.a.field {
+            return Err(Error::FieldMismatch);
+        }
+        if d.is_square() {
+            return Err(Error::QuadraticResidue);
+        }
+        let d2 = d.clone() * d.clone();
+        let d3 = d2.clone() * d;
+        Self::try_new(d2 * self.a.clone(), d3 * self.b.clone())
+    }
+
+    // `#E^d = p + 1 + t`, Hasse's bound keeps it positive
+    pub fn twist_order(&self) -> Result<BigUint, Error> {
+        let twice = BigUint::from(2_u8) * (self.a.field.order.clone() + BigUint::one());
+        Ok(twice - self.points_count()?)
+    }
+
+    pub fn twist_security(&self) -> Result<TwistSecurity, Error> {
+        let order = self.twist_order()?;
+        let (factors, unfactored) = factorize(&order);
+        let largest_prime_factor = factors
+            .iter()
+            .map(|(f, _)| f.clone())
+            .max()
+            .unwrap_or_else(BigUint::one);
+        let security_bits = largest_prime_factor.bits() / 2;
+        Ok(TwistSecurity {
+            order,
+            factors,
+            unfactored,
+            largest_prime_factor,
+            security_bits,
+        })
+    }
+
+    // `Δ = -16(4A^3 + 27B^2)`, curve is singular iff `Δ = 0`
+    pub fn discriminant(&self) -> FieldElement<'a> {
+        let field = self.a.field;
+        let four = field.get(BigUint::from(4_u8));
+        let sixteen = field.get(BigUint::from(16_u8));
+        let twenty_seven = field.get(BigUint::from(27_u8));
+
+        let a3 = self.a.clone() * self.a.clone() * self.a.clone();
+        let b2 = self.b.clone() * self.b.clone();
+        -(sixteen * (four * a3 + twenty_seven * b2))
+    }
+
+    // `j = 1728 * 4A^3 / (4A^3 + 27B^2)`, undefined for singular curves
+    pub fn j_invariant(&self) -> Option<FieldElement<'a>> {
+        let field = self.a.field;
+        let four = field.get(BigUint::from(4_u8));
+        let twenty_seven = field.get(BigUint::from(27_u8));
+        let j_1728 = field.get(BigUint::from(1728_u16));
+
+        let a3 = four * self.a.clone() * self.a.clone() * self.a.clone();
+        let din = a3.clone() + twenty_seven * self.b.clone() * self.b.clone();
+        if din.is_zero() {
+            return None;
+        }
+        Some(j_1728 * a3 / din)
+    }
+
+    // curves `(A, B)` and `(A', B')` are isomorphic over the base field
+    // iff there is `u` such that `A' = u^4 A` and `B' = u^6 B`.
+    // Equal j-invariants only guarantee isomorphism over the closure,
+    // in general curve could still be a (quadratic, or for `j = 0, 1728` quartic/sextic) twist
+    pub fn is_isomorphic(&self, other: &WeierstrassCurve<'a>) -> bool {
+        if self.a.field != other.a.field {
+            return false;
+        }
+        match (self.j_invariant(), other.j_invariant()) {
+            (Some(j1), Some(j2)) if j1 == j2 => {}
+            _ => return false,
         }
+
+        if self.a.is_zero() {
+            // j = 0, `B'/B` has to be a 6th power
+            return is_power_residue(other.b.clone() / self.b.clone(), 6);
+        }
+        if self.b.is_zero() {
+            // j = 1728, `A'/A` has to be a 4th power
+            return is_power_residue(other.a.clone() / self.a.clone(), 4);
+        }
+
+        // `u^2 = (B'/B) / (A'/A)`, and with equal j-invariants `(u^2)^2 = A'/A` holds already
+        let u2 = (other.b.clone() * self.a.clone()) / (self.b.clone() * other.a.clone());
+        u2.is_square()
+    }
+
+    pub fn get_base (&self) -> Point<'a> {
+        self.try_get_base().expect("curve should have a point with `x = 5`")
     }
 
-    pub fn get_base (&self) -> Point {
+    // generator if one was set, otherwise the point with `x = 5` that may not exist
+    pub fn try_get_base (&self) -> Result<Point<'a>, Error> {
+        if let Some(g) = &self.generator {
+            return Ok(g.clone());
+        }
         let x = self.a.field.get(BigUint::from(5_u8));
-        Point {
-            x: x.clone(),
-            y: self.evaluate_y(x).unwrap().0,
+        let (y, _) = self.evaluate_y(x.clone()).ok_or(Error::NoSquareRoot)?;
+        Ok(Point {
+            x,
+            y,
             z: self.a.field.one(),
-        }
+        })
     }
 
     pub fn evaluate_y(
@@ -47,6 +278,76 @@ impl<'a> WeierstrassCurve<'a> {
         )
     }
 
+    // `Y^2 Z = X^3 + AXZ^2 + BZ^3`, point of infinity is on every curve
+    pub fn contains(&self, p: &Point<'a>) -> bool {
+        if p.is_infinity() {
+            return true;
+        }
+        let zz = p.z.clone() * p.z.clone();
+        p.y.clone() * p.y.clone() * p.z.clone()
+            == p.x.clone() * p.x.clone() * p.x.clone()
+                + self.a.clone() * p.x.clone() * zz.clone()
+                + self.b.clone() * zz * p.z.clone()
+    }
+
+    // `[n]P = O` for a point on the curve, requires known order.
+    // These checks use `complete_mul` which accepts any `Z`
+    pub fn is_in_prime_subgroup(&self, p: &Point<'a>) -> Result<bool, Error> {
+        let (n, h) = self.order.as_ref().zip(self.cofactor.as_ref()).ok_or(Error::UnknownOrder)?;
+        if !self.contains(p) {
+            return Ok(false);
+        }
+        // with cofactor 1 every point of the curve is in the subgroup
+        if h.is_one() {
+            return Ok(true);
+        }
+        Ok(self.complete_mul(n.clone(), p.clone()).is_infinity())
+    }
+
+    // `[h]P` maps any point of the curve to the prime subgroup
+    pub fn clear_cofactor(&self, p: Point<'a>) -> Result<Point<'a>, Error> {
+        let h = self.cofactor.as_ref().ok_or(Error::UnknownOrder)?;
+        Ok(self.complete_mul(h.clone(), p))
+    }
+
+    // rejects points that are off the curve, infinity, of small order (`[h]P = O`)
+    // or outside of the prime subgroup. Subgroup checks are skipped when order is not known
+    pub fn validate(&self, p: &Point<'a>) -> Result<(), Error> {
+        if p.is_infinity() {
+            return Err(Error::SmallOrder);
+        }
+        if !self.contains(p) {
+            return Err(Error::NotOnCurve);
+        }
+        if let Some(h) = &self.cofactor {
+            if !h.is_one() && self.complete_mul(h.clone(), p.clone()).is_infinity() {
+                return Err(Error::SmallOrder);
+            }
+        }
+        if self.order.is_some() && !self.is_in_prime_subgroup(p)? {
+            return Err(Error::NotInSubgroup);
+        }
+        Ok(())
+    }
+
+    // affine point that passes `validate`
+    pub fn point(
+        &self,
+        x: FieldElement<'a>,
+        y: FieldElement<'a>,
+    ) -> Result<Point<'a>, Error> {
+        if x.field != self.a.field || y.field != self.a.field {
+            return Err(Error::FieldMismatch);
+        }
+        let p = Point {
+            x,
+            y,
+            z: self.a.field.one(),
+        };
+        self.validate(&p)?;
+        Ok(p)
+    }
+
     fn point_double(
         &self,
         p1: Point<'a>,
@@ -143,6 +444,262 @@ impl<'a> WeierstrassCurve<'a> {
         }
     }
 
+    // https://www.hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html
+    pub fn jacobian_double(
+        &self,
+        p1: JacobianPoint<'a>,
+    ) -> JacobianPoint<'a> {
+        let field = self.a.field;
+        let two = field.get(BigUint::from(2_u8));
+        let three = field.get(BigUint::from(3_u8));
+        let eight = field.get(BigUint::from(8_u8));
+
+        match self.a_kind {
+            // dbl-2009-l
+            CoefficientA::Zero => {
+                let a = p1.x.clone() * p1.x.clone();
+                let b = p1.y.clone() * p1.y.clone();
+                let c = b.clone() * b.clone();
+                let xb = p1.x + b;
+                let d = two.clone() * (xb.clone() * xb - a.clone() - c.clone());
+                let e = three * a;
+                let f = e.clone() * e.clone();
+                let x = f - two.clone() * d.clone();
+                JacobianPoint {
+                    y: e * (d - x.clone()) - eight * c,
+                    z: two * p1.y * p1.z,
+                    x,
+                }
+            }
+            // dbl-2001-b
+            CoefficientA::MinusThree => {
+                let delta = p1.z.clone() * p1.z.clone();
+                let gamma = p1.y.clone() * p1.y.clone();
+                let beta = p1.x.clone() * gamma.clone();
+                let alpha = three * (p1.x.clone() - delta.clone()) * (p1.x + delta.clone());
+                let x = alpha.clone() * alpha.clone() - eight.clone() * beta.clone();
+                let yz = p1.y + p1.z;
+                JacobianPoint {
+                    z: yz.clone() * yz - gamma.clone() - delta,
+                    y: alpha * (field.get(BigUint::from(4_u8)) * beta - x.clone()) - eight * gamma.clone() * gamma,
+                    x,
+                }
+            }
+            // dbl-2007-bl
+            CoefficientA::Generic => {
+                let xx = p1.x.clone() * p1.x.clone();
+                let yy = p1.y.clone() * p1.y.clone();
+                let yyyy = yy.clone() * yy.clone();
+                let zz = p1.z.clone() * p1.z.clone();
+                let xyy = p1.x + yy.clone();
+                let s = two.clone() * (xyy.clone() * xyy - xx.clone() - yyyy.clone());
+                let m = three * xx + self.a.clone() * zz.clone() * zz.clone();
+                let t = m.clone() * m.clone() - two * s.clone();
+                let yz = p1.y + p1.z;
+                JacobianPoint {
+                    y: m * (s - t.clone()) - eight * yyyy,
+                    z: yz.clone() * yz - yy - zz,
+                    x: t,
+                }
+            }
+        }
+    }
+
+    // add-2007-bl, falls back to doubling when `P1 = P2`
+    pub fn jacobian_add(
+        &self,
+        p1: JacobianPoint<'a>,
+        p2: JacobianPoint<'a>,
+    ) -> JacobianPoint<'a> {
+        if p1.is_infinity() {
+            return p2;
+        }
+        if p2.is_infinity() {
+            return p1;
+        }
+        let two = self.a.field.get(BigUint::from(2_u8));
+
+        let z1z1 = p1.z.clone() * p1.z.clone();
+        let z2z2 = p2.z.clone() * p2.z.clone();
+        let u1 = p1.x.clone() * z2z2.clone();
+        let u2 = p2.x * z1z1.clone();
+        let s1 = p1.y.clone() * p2.z.clone() * z2z2.clone();
+        let s2 = p2.y * p1.z.clone() * z1z1.clone();
+        let h = u2 - u1.clone();
+        let r = two.clone() * (s2 - s1.clone());
+        if h.is_zero() {
+            if r.is_zero() {
+                return self.jacobian_double(p1);
+            }
+            return JacobianPoint::infinity(self.a.field);
+        }
+        let i = (two.clone() * h.clone()) * (two.clone() * h.clone());
+        let j = h.clone() * i.clone();
+        let v = u1 * i;
+        let x = r.clone() * r.clone() - j.clone() - two.clone() * v.clone();
+        let zz = p1.z + p2.z;
+        JacobianPoint {
+            y: r * (v - x.clone()) - two * s1 * j,
+            z: (zz.clone() * zz - z1z1 - z2z2) * h,
+            x,
+        }
+    }
+
+    // madd-1998-cmo, `P2` has `Z = 1` which saves 3 multiplications over `project_point_add`.
+    // Falls back to doubling when `P1 = P2`
+    pub fn mixed_add(
+        &self,
+        p1: Point<'a>,
+        p2: AffinePoint<'a>,
+    ) -> Point<'a> {
+        if p2.is_infinity() {
+            return p1;
+        }
+        if p1.is_infinity() {
+            return p2.into();
+        }
+        let two = self.a.field.get(BigUint::from(2_u8));
+
+        let u = p2.y.clone() * p1.z.clone() - p1.y.clone();
+        let v = p2.x.clone() * p1.z.clone() - p1.x.clone();
+        if v.is_zero() {
+            if u.is_zero() {
+                return self.project_point_double(p1);
+            }
+            return Point::infinity(self.a.field);
+        }
+        let uu = u.clone() * u.clone();
+        let vv = v.clone() * v.clone();
+        let vvv = v.clone() * vv.clone();
+        let r = vv * p1.x;
+        let a = uu * p1.z.clone() - vvv.clone() - two * r.clone();
+        Point {
+            x: v * a.clone(),
+            y: u * (r - a) - vvv.clone() * p1.y,
+            z: vvv * p1.z,
+        }
+    }
+
+    // Renes–Costello–Batina complete addition in homogeneous coordinates, https://eprint.iacr.org/2015/1060.
+    // Has no exceptional cases (`P1 = P2`, `P = O`, `P1 = -P2`) on curves without points of order 2,
+    // in particular on any curve of prime order
+    pub fn complete_add(
+        &self,
+        p1: Point<'a>,
+        p2: Point<'a>,
+    ) -> Point<'a> {
+        let b3 = self.a.field.get(BigUint::from(3_u8)) * self.b.clone();
+        let (x1, y1, z1) = (p1.x, p1.y, p1.z);
+        let (x2, y2, z2) = (p2.x, p2.y, p2.z);
+
+        let t0 = x1.clone() * x2.clone();
+        let t1 = y1.clone() * y2.clone();
+        let t2 = z1.clone() * z2.clone();
+        let t3 = (x1.clone() + y1.clone()) * (x2.clone() + y2.clone()) - (t0.clone() + t1.clone());
+
+        match self.a_kind {
+            // algorithm 7
+            CoefficientA::Zero => {
+                let t4 = (y1 + z1.clone()) * (y2 + z2.clone()) - (t1.clone() + t2.clone());
+                let y3 = (x1 + z1) * (x2 + z2) - (t0.clone() + t2.clone());
+                let t0 = t0.clone() + t0.clone() + t0;
+                let t2 = b3.clone() * t2;
+                let z3 = t1.clone() + t2.clone();
+                let t1 = t1 - t2;
+                let y3 = b3 * y3;
+                let x3 = t3.clone() * t1.clone() - t4.clone() * y3.clone();
+                let y3 = t1.clone() * z3.clone() + y3 * t0.clone();
+                let z3 = z3 * t4 + t0 * t3;
+                Point { x: x3, y: y3, z: z3 }
+            }
+            // algorithm 4
+            CoefficientA::MinusThree => {
+                let t4 = (y1 + z1.clone()) * (y2 + z2.clone()) - (t1.clone() + t2.clone());
+                let y3 = (x1 + z1) * (x2 + z2) - (t0.clone() + t2.clone());
+                let z3 = self.b.clone() * t2.clone();
+                let x3 = y3.clone() - z3;
+                let x3 = x3.clone() + x3.clone() + x3;
+                let z3 = t1.clone() - x3.clone();
+                let x3 = t1 + x3;
+                let t2 = t2.clone() + t2.clone() + t2;
+                let y3 = self.b.clone() * y3 - t2.clone() - t0.clone();
+                let y3 = y3.clone() + y3.clone() + y3;
+                let t0 = t0.clone() + t0.clone() + t0 - t2;
+                let x3_out = t3.clone() * x3.clone() - t4.clone() * y3.clone();
+                let y3 = x3 * z3.clone() + t0.clone() * y3;
+                let z3 = t4 * z3 + t3 * t0;
+                Point { x: x3_out, y: y3, z: z3 }
+            }
+            // algorithm 1
+            CoefficientA::Generic => {
+                let t4 = (x1 + z1.clone()) * (x2 + z2.clone()) - (t0.clone() + t2.clone());
+                let t5 = (y1 + z1) * (y2 + z2) - (t1.clone() + t2.clone());
+                let z3 = self.a.clone() * t4.clone() + b3.clone() * t2.clone();
+                let x3 = t1.clone() - z3.clone();
+                let z3 = t1 + z3;
+                let y3 = x3.clone() * z3.clone();
+                let t1 = t0.clone() + t0.clone() + t0.clone();
+                let t2 = self.a.clone() * t2;
+                let t4 = b3 * t4;
+                let t1 = t1 + t2.clone();
+                let t2 = self.a.clone() * (t0 - t2);
+                let t4 = t4 + t2;
+                let y3 = y3 + t1.clone() * t4.clone();
+                let x3 = t3.clone() * x3 - t5.clone() * t4;
+                let z3 = t5 * z3 + t3 * t1;
+                Point { x: x3, y: y3, z: z3 }
+            }
+        }
+    }
+
+    pub fn complete_double(
+        &self,
+        p1: Point<'a>,
+    ) -> Point<'a> {
+        self.complete_add(p1.clone(), p1)
+    }
+
+    // double-and-add without branches on the scalar or on intermediate points,
+    // scans a fixed number of bits - size of the subgroup order if known or `p + 1 + 2√p` bound otherwise
+    pub fn complete_mul(
+        &self,
+        k: BigUint,
+        p1: Point<'a>,
+    ) -> Point<'a> {
+        let bits = self.scalar_bits().max(k.bits());
+        let mut r = Point::infinity(self.a.field);
+        for i in (0..bits).rev() {
+            r = self.complete_double(r);
+            let added = self.complete_add(r.clone(), p1.clone());
+            (r, _) = Point::conditional_swap(k.bit(i), r, added);
+        }
+        r
+    }
+
+    // number of bits enough to represent any scalar reduced by the order of a point
+    pub(crate) fn scalar_bits(&self) -> u64 {
+        match &self.order {
+            Some(n) => n.bits(),
+            // Hasse's bound `#E <= p + 1 + 2√p < 2p` for `p > 5`
+            None => self.a.field.order.bits() + 1,
+        }
+    }
+
+    // scalar reduced by the group order `n * h`, which any point order divides, and the number of bits
+    // ladders scan for it. Without a known order `k` is used as is and its length leaks when above the bound
+    fn ladder_scalar(&self, k: BigUint) -> (BigUint, u64) {
+        match self.order.as_ref().zip(self.cofactor.as_ref()) {
+            Some((n, h)) => {
+                let group = n * h;
+                (k % &group, group.bits())
+            }
+            None => {
+                let bits = self.scalar_bits().max(k.bits());
+                (k, bits)
+            }
+        }
+    }
+
     pub fn double_and_add(
         &self,
         k: BigUint,
@@ -166,58 +723,254 @@ impl<'a> WeierstrassCurve<'a> {
         r0
     }
 
-    pub fn montgomery_ladder(
+    // variable time, leaks the scalar through timing, use for public scalars only.
+    // Table of odd multiples `P, 3P, .., (2^(w-1) - 1)P` is built once, negative digits
+    // reuse it via negation. Width grows with the scalar, 5 for 256-bit scalars
+    pub fn wnaf_mul(
         &self,
         k: BigUint,
         p1: Point<'a>,
     ) -> Point<'a> {
-        // find `n` where `curve order < 2 ^ n`
-        let order = self.find_order(p1.clone());
+        let w = match k.bits() {
+            0..=24 => 2,
+            25..=80 => 3,
+            81..=160 => 4,
+            161..=380 => 5,
+            _ => 6,
+        };
+        self.wnaf_mul_with_width(k, p1, w)
+    }
 
-        let size = u32::BITS as usize;
-        let mut bits = 0;
-        {
-            let mut order_iter = order.iter_u32_digits().rev();
-            if let Some(digit) = order_iter.next() {
-                let bit_iter = BitIter::from(digit).skip_while(|b| b == &false);
-                bits += bit_iter.count();
+    pub fn wnaf_mul_with_width(
+        &self,
+        k: BigUint,
+        p1: Point<'a>,
+        w: usize,
+    ) -> Point<'a> {
+        // same range as `wnaf`, the table has to match the digits
+        let w = w.clamp(2, 16);
+        let digits = wnaf(&k, w);
+        let table = self.odd_multiples(p1.into(), w);
+
+        let mut r = JacobianPoint::infinity(self.a.field);
+        for d in digits.into_iter().rev() {
+            r = self.jacobian_double(r);
+            if d > 0 {
+                r = self.jacobian_add(r, table[(d / 2) as usize].clone());
+            } else if d < 0 {
+                r = self.jacobian_add(r, -table[(-d / 2) as usize].clone());
             }
-            bits += order_iter.count() * size;
         }
+        r.to_point()
+    }
 
-        // convert k to keep `nth` bit always at one
-        // this lets us avoid setting r0 to point of infinity and handling it during operations
-        // by implicitly performing one iteration
-        let k = {
-            let n_2 = BigUint::from(1_u8) << bits;
-            let d = if k > n_2 {
-                (k - n_2.clone()) % order.clone()
-            } else {
-                order.clone() - (n_2.clone() - k) % order.clone()
-            };
+    // `P, 3P, .., (2^(w-1) - 1)P`, table for digits of width-w NAF
+    pub(crate) fn odd_multiples(
+        &self,
+        p1: JacobianPoint<'a>,
+        w: usize,
+    ) -> Vec<JacobianPoint<'a>> {
+        let double = self.jacobian_double(p1.clone());
+        let mut table = Vec::with_capacity(1 << (w - 2));
+        table.push(p1);
+        for i in 1..(1 << (w - 2)) {
+            table.push(self.jacobian_add(table[i - 1].clone(), double.clone()));
+        }
+        table
+    }
 
-            n_2 + d
-        };
+    // scans the same number of bits for every scalar, see `ladder_scalar`.
+    // The pair is swapped with a mask and complete formulas have no exceptional cases,
+    // `R1 - R0 = P` after every step and `R0 = [k]P` at the end, point of infinity for `k = 0 mod n`.
+    // Field arithmetic on `BigUint` is not constant time, so timing still depends on the values
+    pub fn montgomery_ladder(
+        &self,
+        k: BigUint,
+        p1: Point<'a>,
+    ) -> Point<'a> {
+        let (k, bits) = self.ladder_scalar(k);
+        let mut r0 = Point::infinity(self.a.field);
+        let mut r1 = p1;
 
-        let mut r0 = p1.clone();
-        let mut r1 = self.project_point_double(p1.clone());
+        let mut swap = false;
+        for i in (0..bits).rev() {
+            let bit = k.bit(i);
+            swap ^= bit;
+            (r0, r1) = Point::conditional_swap(swap, r0, r1);
+            swap = bit;
 
-        for digit in k.iter_u32_digits().rev() {
-            let count = bits % size;
-            bits -= count;
+            r1 = self.complete_add(r0.clone(), r1);
+            r0 = self.complete_double(r0);
+        }
+        (r0, _) = Point::conditional_swap(swap, r0, r1);
+        r0
+    }
 
-            for b in BitIter::from(digit).skip(size - count) {
-                if b {
-                    r0 = self.project_point_add(r0.clone(), r1.clone());
-                    r1 = self.project_point_double(r1);
-                } else {
-                    r1 = self.project_point_add(r0.clone(), r1.clone());
-                    r0 = self.project_point_double(r0);
-                }
-            }
+    // `(X:Z) -> [2](X:Z)` on x-coordinates, `X' = (X^2 - aZ^2)^2 - 8bXZ^3` and `Z' = 4Z(X^3 + aXZ^2 + bZ^3)`
+    pub fn x_double(
+        &self,
+        x: FieldElement<'a>,
+        z: FieldElement<'a>,
+    ) -> (FieldElement<'a>, FieldElement<'a>) {
+        let field = self.a.field;
+        let four = field.get(BigUint::from(4_u8));
+        let eight = field.get(BigUint::from(8_u8));
+
+        let xx = x.clone() * x.clone();
+        let zz = z.clone() * z.clone();
+        let bzzz = self.b.clone() * zz.clone() * z.clone();
+        let t = xx.clone() - self.a.clone() * zz.clone();
+        (
+            t.clone() * t - eight * x.clone() * bzzz.clone(),
+            four * z * (xx * x.clone() + self.a.clone() * x * zz + bzzz),
+        )
+    }
+
+    // `(X1:Z1) + (X2:Z2)` knowing affine x-coordinate of their difference, Brier-Joye additive formula
+    // `X3 = 2(X1Z2 + X2Z1)(X1X2 + aZ1Z2) + 4bZ1^2Z2^2 - x(X1Z2 - X2Z1)^2` and `Z3 = (X1Z2 - X2Z1)^2`
+    pub fn x_add(
+        &self,
+        (x1, z1): (FieldElement<'a>, FieldElement<'a>),
+        (x2, z2): (FieldElement<'a>, FieldElement<'a>),
+        x: FieldElement<'a>,
+    ) -> (FieldElement<'a>, FieldElement<'a>) {
+        let field = self.a.field;
+        let two = field.get(BigUint::from(2_u8));
+        let four = field.get(BigUint::from(4_u8));
+
+        let x1z2 = x1.clone() * z2.clone();
+        let x2z1 = x2.clone() * z1.clone();
+        let z1z2 = z1 * z2;
+        let d = x1z2.clone() - x2z1.clone();
+        let dd = d.clone() * d;
+        (
+            two * (x1z2 + x2z1) * (x1 * x2 + self.a.clone() * z1z2.clone())
+                + four * self.b.clone() * z1z2.clone() * z1z2
+                - x * dd.clone(),
+            dd,
+        )
+    }
+
+    // `x([k]P)` and `x([k + 1]P)` in `(X:Z)` form given affine `x(P)`, same schedule as `montgomery_ladder`
+    pub fn x_ladder(
+        &self,
+        k: &BigUint,
+        x: FieldElement<'a>,
+    ) -> ((FieldElement<'a>, FieldElement<'a>), (FieldElement<'a>, FieldElement<'a>)) {
+        let field = self.a.field;
+        let (k, bits) = self.ladder_scalar(k.clone());
+        let mut r0 = (field.one(), field.zero());
+        let mut r1 = (x.clone(), field.one());
+
+        let mut swap = false;
+        for i in (0..bits).rev() {
+            let bit = k.bit(i);
+            swap ^= bit;
+            (r0.0, r1.0) = FieldElement::conditional_swap(swap, r0.0, r1.0);
+            (r0.1, r1.1) = FieldElement::conditional_swap(swap, r0.1, r1.1);
+            swap = bit;
+
+            r1 = self.x_add(r0.clone(), r1, x.clone());
+            r0 = self.x_double(r0.0, r0.1);
         }
+        (r0.0, r1.0) = FieldElement::conditional_swap(swap, r0.0, r1.0);
+        (r0.1, r1.1) = FieldElement::conditional_swap(swap, r0.1, r1.1);
+        (r0, r1)
+    }
 
-        r0
+    // Okeya-Sakurai y-recovery of `Q` from `P`, `x(Q)` and `x(Q + P)`:
+    // `y(Q) = (2b + (a + x x1)(x + x1) - x2(x - x1)^2) / 2y`, scaled by `Z1^2 Z2` to avoid inversions.
+    // Branches only when `Q` or `Q + P` is the point of infinity or `P` has order 2
+    pub fn recover_y(
+        &self,
+        p: &AffinePoint<'a>,
+        (x1, z1): (FieldElement<'a>, FieldElement<'a>),
+        (x2, z2): (FieldElement<'a>, FieldElement<'a>),
+    ) -> Point<'a> {
+        let field = self.a.field;
+        if p.is_infinity() || z1.is_zero() {
+            return Point::infinity(field);
+        }
+        if z2.is_zero() {
+            return -Point::from(p.clone());
+        }
+        if p.y.is_zero() {
+            // `Q` is either `O` or `P` and the former was handled above
+            return p.clone().into();
+        }
+        let two = field.get(BigUint::from(2_u8));
+
+        let t = p.x.clone() * z1.clone() - x1.clone();
+        let y = two.clone() * self.b.clone() * z1.clone() * z1.clone() * z2.clone()
+            + z2.clone() * (self.a.clone() * z1.clone() + p.x.clone() * x1.clone()) * (x1.clone() + p.x.clone() * z1.clone())
+            - x2 * t.clone() * t;
+        let s = two * p.y.clone() * z1.clone() * z2;
+        Point {
+            x: s.clone() * x1,
+            y,
+            z: s * z1,
+        }
+    }
+
+    // x-only ladder over a fixed number of bits followed by y-recovery, no branches on the scalar.
+    // Not constant time: `BigUint` field arithmetic and `recover_y` leak timing of intermediate values
+    pub fn mul(
+        &self,
+        k: BigUint,
+        p1: Point<'a>,
+    ) -> Point<'a> {
+        let p = p1.to_affine();
+        if p.is_infinity() {
+            return p1;
+        }
+        let (q, q_next) = self.x_ladder(&k, p.x.clone());
+        self.recover_y(&p, q, q_next)
+    }
+
+    pub fn checked_double_and_add(
+        &self,
+        k: BigUint,
+        p1: Point<'a>,
+    ) -> Result<Point<'a>, Error> {
+        self.validate(&p1)?;
+        // `double_and_add` works on affine points only
+        Ok(self.double_and_add(k, p1.to_affine().into()))
+    }
+
+    pub fn checked_montgomery_ladder(
+        &self,
+        k: BigUint,
+        p1: Point<'a>,
+    ) -> Result<Point<'a>, Error> {
+        self.validate(&p1)?;
+        Ok(self.montgomery_ladder(k, p1))
+    }
+
+    pub fn checked_complete_mul(
+        &self,
+        k: BigUint,
+        p1: Point<'a>,
+    ) -> Result<Point<'a>, Error> {
+        self.validate(&p1)?;
+        Ok(self.complete_mul(k, p1))
+    }
+
+    pub fn checked_wnaf_mul(
+        &self,
+        k: BigUint,
+        p1: Point<'a>,
+    ) -> Result<Point<'a>, Error> {
+        self.validate(&p1)?;
+        Ok(self.wnaf_mul(k, p1))
+    }
+
+    pub fn checked_mul(
+        &self,
+        k: BigUint,
+        p1: Point<'a>,
+    ) -> Result<Point<'a>, Error> {
+        self.validate(&p1)?;
+        Ok(self.mul(k, p1))
     }
 
     // order of a subgroup generated by provided point
@@ -253,20 +1006,19 @@ impl<'a> WeierstrassCurve<'a> {
             return None;
         }
 
-        let result;
-        if p == -q.clone() { // when slope is infinity
-            result = s.x - p.x;
+        let result = if p == -q.clone() { // when slope is infinity
+            s.x - p.x
         } else {
             let slope = if p.x == q.x {
                 let three = self.a.field.get(BigUint::from(3_u8));
-                (three * p.x.clone() * p.x.clone() + self.a.clone()) / (p.y.clone() + p.y.clone())
+                (three * p.x.clone() * p.x.clone() + self.a.clone()).try_div(p.y.clone() + p.y.clone()).ok()?
             } else {
-                (q.y - p.y.clone()) / (q.x.clone() - p.x.clone())
+                (q.y - p.y.clone()).try_div(q.x.clone() - p.x.clone()).ok()?
             };
             let num = s.y - p.y - slope.clone() * (s.x.clone() - p.x.clone());
             let din = s.x + p.x + q.x - slope.clone() * slope;
-            result = num / din;
-        }
+            num.try_div(din).ok()?
+        };
 
         Some(result)
     }
@@ -327,11 +1079,17 @@ impl<'a> WeierstrassCurve<'a> {
     pub fn random_point(
         &self,
     ) -> Option<Point<'a>> {
-        let mut thread_rng = thread_rng();
+        self.random_point_with(&mut thread_rng())
+    }
+
+    pub fn random_point_with<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+    ) -> Option<Point<'a>> {
         let mut bytes = vec![0; self.a.field.order.bits() as usize];
 
         for _ in 0..100 {
-            thread_rng.fill_bytes(&mut bytes);
+            rng.fill_bytes(&mut bytes);
             let x = self.a.field.sample(&bytes);
 
             if let Some(y) = self.evaluate_y(x.clone()) {
@@ -347,21 +1105,28 @@ impl<'a> WeierstrassCurve<'a> {
         None
     }
 
-    fn phi(
+    // endomorphism `(x, y) -> (βx, y)` of curves with `a = 0` where `β` is a cube root of unity,
+    // acts on the prime subgroup as multiplication by `λ`, see `Glv`
+    pub fn phi(
         &self,
         a: Point<'a>,
     ) -> Point<'a> {
-        if a.is_infinity() {
-            return a;
-        }
-        let temp = self.a.field.generator().expect("should have generator");
-        let x = a.x * temp;
-        let y = a.y;
-        Point {
-            x,
-            y,
-            z: self.a.field.one(),
+        self.try_phi(a).expect("curve should have `a = 0` and a cube root of unity")
+    }
+
+    pub fn try_phi(
+        &self,
+        a: Point<'a>,
+    ) -> Result<Point<'a>, Error> {
+        if !self.a.is_zero() {
+            return Err(Error::NoEndomorphism);
         }
+        let beta = self.a.field.cube_root_of_unity().ok_or(Error::NoEndomorphism)?;
+        Ok(Point {
+            x: a.x * beta,
+            y: a.y,
+            z: a.z,
+        })
     }
 
     pub fn weilpairing(
@@ -370,6 +1135,36 @@ impl<'a> WeierstrassCurve<'a> {
         p: Point<'a>,
         q: Point<'a>,
     ) -> Option<FieldElement<'a>> {
+        self.weilpairing_with(m, p, q, &mut thread_rng())
+    }
+
+    pub fn weilpairing_with<R: RngCore + CryptoRng>(
+        &self,
+        m: BigUint,
+        p: Point<'a>,
+        q: Point<'a>,
+        rng: &mut R,
+    ) -> Option<FieldElement<'a>> {
+        self.try_weilpairing_with(m, p, q, rng).ok()
+    }
+
+    pub fn try_weilpairing(
+        &self,
+        m: BigUint,
+        p: Point<'a>,
+        q: Point<'a>,
+    ) -> Result<FieldElement<'a>, Error> {
+        self.try_weilpairing_with(m, p, q, &mut thread_rng())
+    }
+
+    // the auxiliary point `S` is drawn from `rng`, the result does not depend on it
+    pub fn try_weilpairing_with<R: RngCore + CryptoRng>(
+        &self,
+        m: BigUint,
+        p: Point<'a>,
+        q: Point<'a>,
+        rng: &mut R,
+    ) -> Result<FieldElement<'a>, Error> {
         // { // we calculate m using the same function, is there a point to do this check?
         //     // Let `P,Q in E[m]` be points of order `m` in `E`
         //     let m_p = self.find_order(p.clone());
@@ -379,7 +1174,7 @@ impl<'a> WeierstrassCurve<'a> {
         //     }
         // }
 
-        let mut s = self.random_point().expect("failed to gen point");
+        let mut s = self.random_point_with(rng).ok_or(Error::PairingFailed)?;
         {
             let tries = 100;
             let mut i = 0;
@@ -395,7 +1190,7 @@ impl<'a> WeierstrassCurve<'a> {
                         let mut i = BigUint::from(1_u8);
                         let mut add = p.clone();
                         while i <= m {
-                            map_p.insert(format!("({:?},{:?},{:?})", add.x, add.y, add.z), i.clone());
+                            map_p.insert(add.clone(), i.clone());
                             add = self.point_add(add, p.clone());
                             i += BigUint::one();
                         }
@@ -405,7 +1200,7 @@ impl<'a> WeierstrassCurve<'a> {
                         let mut i = BigUint::from(1_u8);
                         let mut add = q.clone();
                         while i <= m {
-                            map_q.insert(format!("({:?},{:?},{:?})", add.x, add.y, add.z), i.clone());
+                            map_q.insert(add.clone(), i.clone());
                             add = self.point_add(add, q.clone());
                             i += BigUint::one();
                         }
@@ -415,8 +1210,7 @@ impl<'a> WeierstrassCurve<'a> {
                         let mut i = BigUint::from(1_u8);
                         let mut add = s.clone();
                         while i <= m {
-                            let key = format!("({:?},{:?},{:?})", add.x, add.y, add.z);
-                            if map_p.contains_key(&key) || map_q.contains_key(&key) {
+                            if map_p.contains_key(&add) || map_q.contains_key(&add) {
                                 is_good = false;
                                 break
                             }
@@ -428,32 +1222,177 @@ impl<'a> WeierstrassCurve<'a> {
                         break
                     }
                 }
-                s = self.random_point().expect("failed to gen point");
+                s = self.random_point_with(rng).ok_or(Error::PairingFailed)?;
                 i += 1;
-            }
-            if i == tries {
-                panic!("failed to generate proper s for provided M");
-                // return None;
+                if i == tries {
+                    return Err(Error::PairingFailed);
+                }
             }
         }
 
-        let mp_q_s  = self.miller(p.clone(), self.point_add(q.clone(),  s.clone()), m.clone())?;
-        let mp_s    = self.miller(p.clone(),                            s.clone() , m.clone())?;
-        let mq_p_ns = self.miller(q.clone(), self.point_add(p.clone(), -s.clone()), m.clone())?;
-        let mq_ns   = self.miller(q.clone(),                           -s.clone() , m.clone())?;
+        let mp_q_s  = self.miller(p.clone(), self.point_add(q.clone(),  s.clone()), m.clone()).ok_or(Error::PairingFailed)?;
+        let mp_s    = self.miller(p.clone(),                            s.clone() , m.clone()).ok_or(Error::PairingFailed)?;
+        let mq_p_ns = self.miller(q.clone(), self.point_add(p.clone(), -s.clone()), m.clone()).ok_or(Error::PairingFailed)?;
+        let mq_ns   = self.miller(q.clone(),                           -s.clone() , m.clone()).ok_or(Error::PairingFailed)?;
 
         // it is `e_m(P,S)'` as inverse of weil pairing `e_m(P,S)` and, in fact, they are equal,
         // but it has all the same properties so application-wise we can use any of them
-        Some((mp_q_s / mp_s) / (mq_p_ns / mq_ns))
+        mp_q_s.try_div(mp_s)?.try_div(mq_p_ns.try_div(mq_ns)?)
     }
 }
 
+// `x` is a k-th power in `F_p^*` iff `x^((p-1)/gcd(k,p-1)) = 1`
+fn is_power_residue(x: FieldElement<'_>, k: u8) -> bool {
+    let phi = x.field.order.clone() - BigUint::one();
+    let g = u_gcd(&BigUint::from(k), &phi);
+    (x ^ (phi / g)).is_one()
+}
+
 #[cfg(test)]
 mod tests {
-    use num_bigint::BigUint;
+    use std::collections::HashSet;
+    use num_bigint::{BigInt, BigUint};
+    use num_traits::{One, Zero};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use crate::curves::fixed_base::FixedBaseTable;
+    use crate::curves::named::SECP256K1;
     use crate::curves::point::Point;
     use crate::curves::weierstass::WeierstrassCurve;
+    use crate::error::Error;
     use crate::field::field::Field;
+    use crate::test_utils::weierstrass_points;
+
+    #[test]
+    fn singular() {
+        let field = Field::new(BigUint::from(61_u8));
+
+        assert!(WeierstrassCurve::try_new(field.get(BigUint::from(9_u8)), field.one()).is_ok());
+        // y^2 = x^3
+        assert_eq!(
+            WeierstrassCurve::try_new(field.zero(), field.zero()).err(),
+            Some(Error::SingularCurve),
+        );
+        // y^2 = x^3 - 3x + 2 = (x - 1)^2 (x + 2)
+        assert_eq!(
+            WeierstrassCurve::try_new(-field.get(BigUint::from(3_u8)), field.get(BigUint::from(2_u8))).err(),
+            Some(Error::SingularCurve),
+        );
+
+        let other = Field::new(BigUint::from(67_u8));
+        assert_eq!(
+            WeierstrassCurve::try_new(field.one(), other.one()).err(),
+            Some(Error::FieldMismatch),
+        );
+    }
+
+    #[test]
+    fn invariants() {
+        let field = Field::new(BigUint::from(61_u8));
+
+        let e = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one());
+        // -16 * (4 * 729 + 27) = -47088 = 4 (mod 61)
+        assert_eq!(e.discriminant(), field.get(BigUint::from(4_u8)));
+        // 1728 * 2916 / 2943 = 45 (mod 61)
+        assert_eq!(e.j_invariant(), Some(field.get(BigUint::from(45_u8))));
+
+        let e = WeierstrassCurve::new(field.zero(), field.get(BigUint::from(7_u8)));
+        assert_eq!(e.j_invariant(), Some(field.zero()));
+
+        let e = WeierstrassCurve::new(field.get(BigUint::from(5_u8)), field.zero());
+        assert_eq!(e.j_invariant(), Some(field.get(BigUint::from(1728_u16))));
+
+        let e = WeierstrassCurve::new(field.zero(), field.zero());
+        assert_eq!(e.j_invariant(), None);
+    }
+
+    #[test]
+    fn isomorphic() {
+        let field = Field::new(BigUint::from(61_u8));
+        let a = field.get(BigUint::from(9_u8));
+        let b = field.one();
+        let e = WeierstrassCurve::new(a.clone(), b.clone());
+
+        let u = field.get(BigUint::from(10_u8));
+        let u2 = u.clone() * u.clone();
+        let u4 = u2.clone() * u2.clone();
+        let u6 = u4.clone() * u2.clone();
+        let iso = WeierstrassCurve::new(u4 * a.clone(), u6 * b.clone());
+        assert!(e.is_isomorphic(&iso));
+        assert!(iso.is_isomorphic(&e));
+
+        // 2 is not a square mod 61, so this is a quadratic twist
+        let d = field.get(BigUint::from(2_u8));
+        assert!(!d.is_square());
+        let twist = WeierstrassCurve::new(d.clone() * d.clone() * a, d.clone() * d.clone() * d * b);
+        assert_eq!(e.j_invariant(), twist.j_invariant());
+        assert!(!e.is_isomorphic(&twist));
+
+        let other = WeierstrassCurve::new(field.one(), field.one());
+        assert!(!e.is_isomorphic(&other));
+
+        // j = 0: `B' = 8B`, 8 = 2^3 is not a 6th power mod 61 as 2 is not a square
+        let e = WeierstrassCurve::new(field.zero(), field.one());
+        assert!(e.is_isomorphic(&WeierstrassCurve::new(field.zero(), field.get(BigUint::from(64_u8)))));
+        assert!(!e.is_isomorphic(&WeierstrassCurve::new(field.zero(), field.get(BigUint::from(8_u8)))));
+    }
+
+    #[test]
+    fn twist() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one());
+        // small field, counted directly
+        assert_eq!(e.points_count(), Ok(BigUint::from(73_u8)));
+        assert_eq!(e.twist_order(), Ok(BigUint::from(51_u8)));
+        let large = Field::new(BigUint::from(65537_u32));
+        let large = WeierstrassCurve::new(large.one(), large.one());
+        assert_eq!(large.points_count(), Err(Error::UnknownOrder));
+        // order does not fit into Hasse's bound `|t| <= 2√61`
+        let wrong = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one())
+            .with_order(BigUint::from(73_u8), BigUint::from(2_u8));
+        assert_eq!(wrong.twist_order(), Err(Error::InvalidOrder));
+        assert_eq!(wrong.twist_security(), Err(Error::InvalidOrder));
+
+        let e = e.with_order(BigUint::from(73_u8), BigUint::one());
+        assert_eq!(e.points_count(), Ok(BigUint::from(73_u8)));
+        assert_eq!(e.trace(), Ok(BigInt::from(-11)));
+        assert_eq!(e.twist_order(), Ok(BigUint::from(51_u8)));
+
+        assert_eq!(e.quadratic_twist(field.get(BigUint::from(4_u8))).err(), Some(Error::QuadraticResidue));
+        let twist = e.quadratic_twist(field.non_residue().unwrap()).unwrap();
+        assert_eq!(twist.points_count(), e.twist_order());
+        assert!(!twist.is_isomorphic(&e));
+        // twist of a twist is isomorphic to the original curve
+        assert!(twist.quadratic_twist(field.non_residue().unwrap()).unwrap().is_isomorphic(&e));
+
+        let report = e.twist_security().unwrap();
+        assert_eq!(report.order, BigUint::from(51_u8));
+        assert_eq!(report.factors, vec![(BigUint::from(3_u8), 1), (BigUint::from(17_u8), 1)]);
+        assert_eq!(report.unfactored, BigUint::one());
+        assert_eq!(report.largest_prime_factor, BigUint::from(17_u8));
+    }
+
+    #[test]
+    fn twist_security_secp256k1() {
+        let p = BigUint::parse_bytes(b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F", 16).unwrap();
+        let n = BigUint::parse_bytes(b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141", 16).unwrap();
+        let field = Field::new(p);
+        let e = WeierstrassCurve::new(field.zero(), field.get(BigUint::from(7_u8)))
+            .with_order(n, BigUint::one());
+
+        let report = e.twist_security().unwrap();
+        let largest = BigUint::parse_bytes(b"1013176677300131846900870239606035638738100997248092069256697437031", 10).unwrap();
+        assert_eq!(report.factors, vec![
+            (BigUint::from(3_u8), 2),
+            (BigUint::from(13_u8), 2),
+            (BigUint::from(3319_u16), 1),
+            (BigUint::from(22639_u16), 1),
+            (largest.clone(), 1),
+        ]);
+        assert_eq!(report.unfactored, BigUint::one());
+        assert_eq!(report.largest_prime_factor, largest);
+        assert_eq!(report.security_bits, 110);
+    }
 
     #[test]
     fn eval_divisor() {
@@ -596,9 +1535,10 @@ mod tests {
         // any `m` will be co-prime to `char(F)` which is prime, no need to check that
         assert_eq!(m, e.find_order(q.clone()));
 
-        for test in 0..1_000_000 {
-            println!("#{test}");
-            let pairing = e.weilpairing(m.clone(), p.clone(), q.clone());
+        // every seed is its own reproducible run, a failure names the seed to replay
+        for seed in 0..256 {
+            let rng = &mut StdRng::seed_from_u64(seed);
+            let pairing = e.weilpairing_with(m.clone(), p.clone(), q.clone(), rng);
 
             // properties
             { // non-degenerate
@@ -613,29 +1553,300 @@ mod tests {
             { // bilinear
                 let a = BigUint::from(7_u8);
                 assert_eq!(
-                e.weilpairing(m.clone(), e.double_and_add(a.clone(), p.clone()), q.clone()),
+                e.weilpairing_with(m.clone(), e.double_and_add(a.clone(), p.clone()), q.clone(), rng),
                 Some(pairing.clone() ^ a.clone()),
             );
                 assert_eq!(
-                e.weilpairing(m.clone(), p.clone(), e.double_and_add(a.clone(), q.clone())),
+                e.weilpairing_with(m.clone(), p.clone(), e.double_and_add(a.clone(), q.clone()), rng),
                 Some(pairing.clone() ^ a.clone()),
             );
             }
             {// alternating
                 let a = BigUint::from(7_u8);
                 assert_eq!(
-                e.weilpairing(m.clone(), p.clone(), p.clone()),
+                e.weilpairing_with(m.clone(), p.clone(), p.clone(), rng),
                 Some(field.one()),
             );
                 let p2 = e.double_and_add(a.clone(), p.clone());
                 assert_eq!(
-                e.weilpairing(m.clone(), p2.clone(), p2.clone()),
+                e.weilpairing_with(m.clone(), p2.clone(), p2.clone(), rng),
                 Some(field.one()),
             );
             }
         }
     }
 
+    #[test]
+    fn random_point() {
+        let field = SECP256K1.field();
+        let e = SECP256K1.curve(&field);
+
+        let p = e.random_point_with(&mut StdRng::seed_from_u64(3)).unwrap();
+        assert!(e.contains(&p));
+        assert_eq!(e.random_point_with(&mut StdRng::seed_from_u64(3)), Some(p));
+        assert!(e.contains(&e.random_point().unwrap()));
+    }
+
+    #[test]
+    fn fallible() {
+        let field = Field::new(BigUint::from(61_u8));
+        // `5^3 + 45 + 2 = 50` is not a square mod 61
+        let e = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.get(BigUint::from(2_u8)));
+        assert_eq!(e.try_get_base(), Err(Error::NoSquareRoot));
+        assert_eq!(e.try_phi(Point::infinity(&field)), Err(Error::NoEndomorphism));
+
+        // prime order 73, every `S` lies in the subgroup of `P = Q`
+        let e = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one());
+        let g = e.try_get_base().unwrap();
+        let m = e.find_order(g.clone());
+        let rng = &mut StdRng::seed_from_u64(0);
+        assert_eq!(e.try_weilpairing_with(m.clone(), g.clone(), g.clone(), rng), Err(Error::PairingFailed));
+        assert_eq!(e.weilpairing_with(m, g.clone(), g, rng), None);
+
+        let field = SECP256K1.field();
+        let e = SECP256K1.curve(&field);
+        let g = e.get_base();
+        assert_eq!(e.try_phi(g.clone()), Ok(e.phi(g)));
+    }
+
+    fn affine(p: Point<'_>) -> Point<'_> {
+        p.to_affine().into()
+    }
+
+    #[test]
+    fn complete_formulas() {
+        let field = Field::new(BigUint::from(61_u8));
+        // all have odd order: 73, 61 and 71 points
+        let curves = [
+            WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one()),
+            WeierstrassCurve::new(field.zero(), field.get(BigUint::from(7_u8))),
+            WeierstrassCurve::new(-field.get(BigUint::from(3_u8)), field.get(BigUint::from(4_u8))),
+        ];
+        let scale = field.get(BigUint::from(17_u8));
+
+        for e in &curves {
+            let points = weierstrass_points(e);
+            for p in &points {
+                let sp = Point {
+                    x: p.x.clone() * scale.clone(),
+                    y: p.y.clone() * scale.clone(),
+                    z: p.z.clone() * scale.clone(),
+                };
+                assert_eq!(affine(e.complete_double(sp.clone())), e.point_add(p.clone(), p.clone()));
+                for q in &points {
+                    assert_eq!(
+                        affine(e.complete_add(sp.clone(), q.clone())),
+                        e.point_add(p.clone(), q.clone()),
+                    );
+                }
+            }
+
+            let base = points[1].clone();
+            for k in [0_u32, 1, 2, 3, 60, 61, 72, 73, 74, 1000] {
+                assert_eq!(
+                    affine(e.complete_mul(BigUint::from(k), base.clone())),
+                    e.double_and_add(BigUint::from(k), base.clone()),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn mixed_addition() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one());
+        let scale = field.get(BigUint::from(17_u8));
+
+        let points = weierstrass_points(&e);
+        for p in &points {
+            let sp = Point {
+                x: p.x.clone() * scale.clone(),
+                y: p.y.clone() * scale.clone(),
+                z: p.z.clone() * scale.clone(),
+            };
+            assert_eq!(sp.to_affine(), p.to_affine());
+            assert_eq!(Point::from(sp.to_affine()), p.clone());
+            for q in &points {
+                assert_eq!(
+                    e.mixed_add(sp.clone(), q.to_affine()).to_affine(),
+                    e.point_add(p.clone(), q.clone()).to_affine(),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn projective_equality() {
+        let field = Field::new(BigUint::from(61_u8));
+        let e = WeierstrassCurve::new(field.get(BigUint::from(9_u8)), field.one());
+        let base = e.get_base();
+
+        fn scaled<'a>(p: &Point<'a>, l: u8) -> Point<'a> {
+            let l = p.x.field.get(BigUint::from(l));
+            Point {
+                x: p.x.clone() * l.clone(),
+                y: p.y.clone() * l.clone(),
+                z: p.z.clone() * l,
+            }
+        }
+        assert_eq!(base, scaled(&base, 17));
+        assert_ne!(base, scaled(&-base.clone(), 17));
+        assert_ne!(base, Point::infinity(&field));
+        assert_eq!(Point::infinity(&field), scaled(&Point::infinity(&field), 17));
+
+        let p = e.project_point_double(base.clone());
+        assert!(!p.z.is_one());
+        assert_eq!(p.canonical(), e.point_add(base.clone(), base.clone()));
+        assert!(p.canonical().z.is_one());
+
+        let mut set = HashSet::new();
+        set.insert(p);
+        assert!(set.contains(&e.point_add(base.clone(), base.clone())));
+        assert!(set.contains(&scaled(&e.point_add(base.clone(), base.clone()), 5)));
+        assert!(!set.contains(&base));
+    }
+
+    #[test]
+    fn validation() {
+        let field = Field::new(BigUint::from(631_u16));
+        // 650 = 5 * 130 points, `S` below generates subgroup of order 130 = 2 * 5 * 13
+        let e = WeierstrassCurve::new(field.get(BigUint::from(30_u8)), field.get(BigUint::from(34_u8)));
+        assert_eq!(e.points_count(), Ok(BigUint::from(650_u16)));
+        assert_eq!(e.is_in_prime_subgroup(&Point::infinity(&field)), Err(Error::UnknownOrder));
+
+        let e = e.with_order(BigUint::from(13_u8), BigUint::from(50_u8));
+        let s = Point {
+            x: field.get(BigUint::from(0_u8)),
+            y: field.get(BigUint::from(36_u8)),
+            z: field.one(),
+        };
+        assert!(e.contains(&s));
+        assert_eq!(e.point(field.get(BigUint::from(0_u8)), field.get(BigUint::from(37_u8))), Err(Error::NotOnCurve));
+
+        // `P` has order 5 which divides cofactor
+        let p = Point {
+            x: field.get(BigUint::from(36_u8)),
+            y: field.get(BigUint::from(60_u8)),
+            z: field.one(),
+        };
+        assert!(e.contains(&p));
+        assert_eq!(e.validate(&p), Err(Error::SmallOrder));
+        assert_eq!(e.validate(&Point::infinity(&field)), Err(Error::SmallOrder));
+        assert_eq!(e.is_in_prime_subgroup(&p), Ok(false));
+
+        // `[h]S` has order 13
+        let cleared: Point = e.clear_cofactor(s.clone()).unwrap().to_affine().into();
+        assert_eq!(e.find_order(cleared.clone()), BigUint::from(13_u8));
+        assert_eq!(e.is_in_prime_subgroup(&cleared), Ok(true));
+        assert_eq!(e.validate(&cleared), Ok(()));
+        // order 130 is neither small nor prime
+        assert_eq!(e.validate(&s), Err(Error::NotInSubgroup));
+
+        let off = Point {
+            x: field.get(BigUint::from(1_u8)),
+            y: field.get(BigUint::from(1_u8)),
+            z: field.one(),
+        };
+        assert_eq!(e.checked_double_and_add(BigUint::from(3_u8), off.clone()), Err(Error::NotOnCurve));
+        assert_eq!(e.checked_complete_mul(BigUint::from(3_u8), p.clone()), Err(Error::SmallOrder));
+        assert_eq!(
+            e.checked_double_and_add(BigUint::from(3_u8), cleared.clone()),
+            Ok(e.double_and_add(BigUint::from(3_u8), cleared)),
+        );
+    }
+
+    #[test]
+    fn validation_projective() {
+        let field = Field::new(BigUint::from(631_u16));
+        let e = WeierstrassCurve::new(field.get(BigUint::from(30_u8)), field.get(BigUint::from(34_u8)))
+            .with_order(BigUint::from(13_u8), BigUint::from(50_u8));
+        fn scaled<'a>(p: &Point<'a>) -> Point<'a> {
+            let l = p.x.field.get(BigUint::from(7_u8));
+            Point {
+                x: p.x.clone() * l.clone(),
+                y: p.y.clone() * l.clone(),
+                z: p.z.clone() * l,
+            }
+        }
+        // order 130, see `validation`
+        let s = Point {
+            x: field.get(BigUint::from(0_u8)),
+            y: field.get(BigUint::from(36_u8)),
+            z: field.one(),
+        };
+        let g = e.clear_cofactor(s.clone()).unwrap().to_affine().into();
+
+        // `Z != 1` for points in the subgroup
+        for p in [scaled(&g), e.project_point_double(g.clone())] {
+            assert!(!p.z.is_one());
+            assert_eq!(e.is_in_prime_subgroup(&p), Ok(true));
+            assert_eq!(e.validate(&p), Ok(()));
+            assert_eq!(e.clear_cofactor(p.clone()), e.clear_cofactor(Point::from(p.to_affine())));
+            assert_eq!(e.checked_double_and_add(BigUint::from(5_u8), p.clone()), Ok(e.complete_mul(BigUint::from(5_u8), p.clone())));
+        }
+
+        let p = scaled(&s);
+        assert_eq!(e.is_in_prime_subgroup(&p), Ok(false));
+        assert_eq!(e.validate(&p), Err(Error::NotInSubgroup));
+        assert_eq!(e.checked_wnaf_mul(BigUint::from(2_u8), p.clone()), Err(Error::NotInSubgroup));
+        assert_eq!(FixedBaseTable::checked_new(&e, p.clone(), 2), Err(Error::NotInSubgroup));
+        assert_eq!(e.checked_multi_scalar_mul(&[(BigUint::one(), g.clone()), (BigUint::one(), p.clone())]), Err(Error::NotInSubgroup));
+        assert_eq!(e.checked_mul(BigUint::from(2_u8), p.clone()), Err(Error::NotInSubgroup));
+        assert_eq!(e.validate(&e.clear_cofactor(p).unwrap()), Ok(()));
+    }
+
+    #[test]
+    fn wnaf_mul() {
+        let field = Field::new(BigUint::from(61_u8));
+        for (a, b) in [(9_u8, 1_u8), (0, 7), (58, 4)] {
+            let e = WeierstrassCurve::new(field.get(BigUint::from(a)), field.get(BigUint::from(b)));
+            for p in weierstrass_points(&e) {
+                let mut expected = Point::infinity(&field);
+                for k in 0_u8..30 {
+                    for w in 2..=4 {
+                        assert_eq!(e.wnaf_mul_with_width(BigUint::from(k), p.clone(), w), expected);
+                    }
+                    expected = e.point_add(expected, p.clone());
+                }
+            }
+        }
+
+        let field = SECP256K1.field();
+        let e = SECP256K1.curve(&field);
+        let k = BigUint::parse_bytes(b"7FFFFFC03FFFC003FFFFFC007FFF00000000070000100000000E00FFFFFFF3FF", 16).unwrap();
+        assert_eq!(e.wnaf_mul(k.clone(), e.get_base()), e.double_and_add(k, e.get_base()));
+    }
+
+    #[test]
+    fn ladders() {
+        let field = Field::new(BigUint::from(61_u8));
+        for (a, b) in [(9_u8, 1_u8), (0, 7), (58, 4)] {
+            let e = WeierstrassCurve::new(field.get(BigUint::from(a)), field.get(BigUint::from(b)));
+            for p in weierstrass_points(&e) {
+                let mut expected = Point::infinity(&field);
+                for k in 0_u8..80 {
+                    assert_eq!(e.montgomery_ladder(BigUint::from(k), p.clone()), expected);
+                    assert_eq!(e.mul(BigUint::from(k), p.clone()), expected);
+                    expected = e.point_add(expected, p.clone());
+                }
+            }
+        }
+
+        let field = SECP256K1.field();
+        let e = SECP256K1.curve(&field);
+        let g = e.get_base();
+        let n = SECP256K1.order();
+        let k = BigUint::parse_bytes(b"41FFC1FFFFFE01FFFC0003FFFE0007C001FFF00003FFF07FFE0007C000000003", 16).unwrap();
+        assert_eq!(e.mul(k.clone(), g.clone()), e.wnaf_mul(k.clone(), g.clone()));
+        assert_eq!(e.montgomery_ladder(k.clone(), g.clone()), e.wnaf_mul(k, g.clone()));
+        for k in [BigUint::zero(), n.clone()] {
+            assert!(e.mul(k.clone(), g.clone()).is_infinity());
+            assert!(e.montgomery_ladder(k, g.clone()).is_infinity());
+        }
+        assert_eq!(e.mul(n.clone() - BigUint::one(), g.clone()), -g.clone());
+        assert_eq!(e.mul(n + BigUint::one(), g.clone()), g);
+    }
+
     #[test]
     fn adds_n_mults() {
         let field = Field::new(BigUint::from(61_u8));
@@ -677,31 +1888,31 @@ mod tests {
             y: field.get(BigUint::from(26_u8)),
             z: field.one(),
         });
+        let point = |x: u8, y: u8| Point {
+            x: field.get(BigUint::from(x)),
+            y: field.get(BigUint::from(y)),
+            z: field.one(),
+        };
         {
             let p = e.project_point_double(base.clone()); // 2
-            assert_eq!(p.x.clone() / p.z.clone(), field.get(BigUint::from(26_u8)));
-            assert_eq!(p.y.clone() / p.z.clone(), field.get(BigUint::from(50_u8)));
+            assert_eq!(p, point(26, 50));
 
             let p = e.project_point_add(p, base.clone()); // 3
-            assert_eq!(p.x.clone() / p.z.clone(), field.get(BigUint::from(27_u8)));
-            assert_eq!(p.y.clone() / p.z.clone(), field.get(BigUint::from(38_u8)));
+            assert_eq!(p, point(27, 38));
 
             let p = e.project_point_add( // 5
                 e.project_point_double(p), // 6
                 -base.clone(), // -1
             );
-            assert_eq!(p.x.clone() / p.z.clone(), field.get(BigUint::from(30_u8)));
-            assert_eq!(p.y.clone() / p.z.clone(), field.get(BigUint::from(59_u8)));
+            assert_eq!(p, point(30, 59));
         }
 
         {
             let p = e.montgomery_ladder(BigUint::from(5_u8), base.clone());
-            assert_eq!(p.x / p.z.clone(), field.get(BigUint::from(30_u8)));
-            assert_eq!(p.y / p.z.clone(), field.get(BigUint::from(59_u8)));
+            assert_eq!(p, point(30, 59));
 
             let p = e.montgomery_ladder(BigUint::from(142_u8), base.clone());
-            assert_eq!(p.x / p.z.clone(), field.get(BigUint::from(48_u8)));
-            assert_eq!(p.y / p.z.clone(), field.get(BigUint::from(26_u8)));
+            assert_eq!(p, point(48, 26));
         }
     }
 }
@@ -0,0 +1,71 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    // elements are expected to belong to the same field
+    FieldMismatch,
+    // `4a^3 + 27b^2 = 0`, curve has a cusp or a node
+    SingularCurve,
+    // element was expected to be a quadratic non-residue
+    QuadraticResidue,
+    // operation is not defined in characteristic 2 or 3
+    UnsupportedCharacteristic,
+    // point does not satisfy the curve equation
+    NotOnCurve,
+    // birational map sends the point to a point at infinity of the target curve, it has no affine image
+    ExceptionalPoint,
+    // element is not a square in its field
+    NoSquareRoot,
+    // point of infinity or a point whose order divides the cofactor
+    SmallOrder,
+    // point is on the curve but not in the prime order subgroup
+    NotInSubgroup,
+    // operation requires order of the curve that was not provided
+    UnknownOrder,
+    // operation requires the standard generator of the curve that was not provided
+    UnknownGenerator,
+    // order and cofactor do not satisfy Hasse's bound for the curve
+    InvalidOrder,
+    // bytes or digits do not follow the expected format
+    InvalidEncoding,
+    // curve has no endomorphism with cheap evaluation, GLV needs `a = 0` and `p = 1 mod 3`
+    NoEndomorphism,
+    // empty domain separation tag or more output than `expand_message` can produce
+    InvalidExpansion,
+    // division by the zero element
+    DivisionByZero,
+    // no suitable auxiliary point was found, or `P` and `Q` share a subgroup with it
+    PairingFailed,
+    // secret scalar is zero or not below the group order
+    InvalidScalar,
+    // signature is out of range or does not match the message and public key
+    InvalidSignature,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::FieldMismatch => write!(f, "elements belong to different fields"),
+            Error::SingularCurve => write!(f, "curve is singular"),
+            Error::QuadraticResidue => write!(f, "element is a quadratic residue"),
+            Error::UnsupportedCharacteristic => write!(f, "unsupported field characteristic"),
+            Error::NotOnCurve => write!(f, "point is not on the curve"),
+            Error::ExceptionalPoint => write!(f, "point has no image on the target curve"),
+            Error::NoSquareRoot => write!(f, "element has no square root"),
+            Error::SmallOrder => write!(f, "point has small order"),
+            Error::NotInSubgroup => write!(f, "point is not in the prime order subgroup"),
+            Error::UnknownOrder => write!(f, "curve order is unknown"),
+            Error::UnknownGenerator => write!(f, "curve generator is unknown"),
+            Error::InvalidOrder => write!(f, "curve order is out of Hasse's bound"),
+            Error::InvalidEncoding => write!(f, "invalid encoding"),
+            Error::NoEndomorphism => write!(f, "curve has no efficient endomorphism"),
+            Error::InvalidExpansion => write!(f, "invalid message expansion parameters"),
+            Error::DivisionByZero => write!(f, "division by zero"),
+            Error::PairingFailed => write!(f, "failed to evaluate the pairing"),
+            Error::InvalidScalar => write!(f, "scalar is out of range"),
+            Error::InvalidSignature => write!(f, "invalid signature"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -2,9 +2,7 @@ use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use num_bigint::{BigInt, BigUint};
 use num_traits::{One, Zero};
-use rand::{RngCore, thread_rng};
 use crate::field::field_element::FieldElement;
-use crate::utils::s_tonelli::tonelli_shanks;
 use crate::utils::xgcd::u_xgcd;
 
 #[cfg(test)]
@@ -31,29 +29,36 @@ impl<'a> Field {
     }
   }
 
-  pub fn get(&self, v: BigUint) -> FieldElement {
+  pub fn get(&self, v: BigUint) -> FieldElement<'_> {
     FieldElement::new(self, v % self.order.clone())
   }
 
   pub fn zero (&'a self) -> FieldElement<'a> {
     FieldElement {
-      field: &self,
+      field: self,
       value: BigUint::zero(),
     }
   }
 
   pub fn one (&'a self) -> FieldElement<'a> {
     FieldElement {
-      field: &self,
+      field: self,
       value: BigUint::from(1_u8),
     }
   }
 
+  // number of bytes in the canonical encoding of an element
+  pub fn byte_len(&self) -> usize {
+    (self.order.bits() as usize).div_ceil(8)
+  }
+
+  // plain reduction of big-endian bytes, biased unless `bytes` is much longer than `p`,
+  // see `hash_to_field` for uniform elements
   pub fn sample (&self, bytes: &[u8]) -> FieldElement<'_> {
     let res = bytes
         .iter()
         .fold(BigUint::zero(), |acc, b| {
-          (acc << 8) ^ BigUint::from(*b)
+          (acc << 8) | BigUint::from(*b)
         });
 
     FieldElement::new(self, res % self.order.clone())
@@ -94,7 +99,37 @@ impl<'a> Field {
       }
       res.value += BigUint::one();
     }
-    return None;
+    None
+  }
+
+  // smallest quadratic non-residue, there is none in `F_2`
+  pub fn non_residue(&self) -> Option<FieldElement<'_>> {
+    let mut res = self.get(BigUint::from(2_u8));
+    while res.value < self.order {
+      if !res.is_square() {
+        return Some(res);
+      }
+      res.value += BigUint::one();
+    }
+    None
+  }
+
+  // primitive cube root of unity `g^((p-1)/3)` for the smallest `g` that gives one,
+  // exists iff `p = 1 mod 3`, the other one is its square
+  pub fn cube_root_of_unity(&self) -> Option<FieldElement<'_>> {
+    let three = BigUint::from(3_u8);
+    if !(self.order.clone() % three.clone()).is_one() {
+      return None;
+    }
+    let exp = (self.order.clone() - BigUint::one()) / three;
+    let mut g = self.get(BigUint::from(2_u8));
+    loop {
+      let res = g.clone() ^ exp.clone();
+      if !res.is_one() {
+        return Some(res);
+      }
+      g.value += BigUint::one();
+    }
   }
 
   pub(crate) fn sub_mod (&self, a: BigUint, b: BigUint) -> BigUint {
@@ -110,40 +145,9 @@ impl<'a> Field {
   }
 
   pub(crate) fn mul_mod (&self, a: BigUint, b: BigUint) -> BigUint {
-    let big_zero = BigUint::zero();
-    let big_one = BigUint::from(1_u8);
-
-    let mut res = BigUint::zero();
-
-    let mut a = a;
-    let mut b = b;
-
-    while b > big_zero {
-      if b.clone() & big_one.clone() == big_one {
-        res = self.add_mod(res, a.clone());
-      }
-
-      a = self.add_mod(a.clone(), a);
-
-      b /= BigUint::from(2_u8);
-    }
-
-    // // source - https://www.youtube.com/watch?v=9hSmQtL49g4&ab_channel=DG
-    // // doesnt work
-    // while a != 0 {
-    //   if a & 1 == 1  {
-    //     res ^= b;
-    //   }
-    //
-    //   a >>= 1;
-    //   b <<= 1;
-    //
-    //   if degree(b) == self.degree {
-    //     b ^= self.prime;
-    //   }
-    // }
-
-    res
+    // `BigUint` doesn't overflow, so the product is reduced once
+    // instead of the double-and-add that was used with `u128` in the tutorial
+    (a * b) % &self.order
   }
 
   pub(crate) fn neg_mod (&self, a: BigUint) -> BigUint {
@@ -170,6 +174,7 @@ impl<'a> Field {
 #[cfg(test)]
 mod tests {
   use std::collections::HashMap;
+  use crate::utils::s_tonelli::tonelli_shanks;
   use super::*;
 
   #[test]
@@ -227,4 +232,39 @@ mod tests {
       assert_eq!(BigUint::from(map.len()), field.order - BigUint::one(), "primitive root does not generate full field");
     }
   }
+
+  #[test]
+  fn non_residue () {
+    assert_eq!(Field::new(BigUint::from(2_u8)).non_residue(), None);
+
+    let field = Field::new(BigUint::from(7_u8));
+    assert_eq!(field.non_residue(), Some(field.get(BigUint::from(3_u8))));
+
+    let field = Field::new(BigUint::from(61_u8));
+    assert_eq!(field.non_residue(), Some(field.get(BigUint::from(2_u8))));
+
+    let field = Field::new(get_field_prime());
+    let d = field.non_residue().unwrap();
+    assert!(tonelli_shanks(d).is_none());
+  }
+
+  #[test]
+  fn sample () {
+    let field = Field::new(get_field_prime());
+    assert_eq!(field.byte_len(), 16);
+    assert_eq!(field.sample(&[1, 2, 3]).value, BigUint::from(0x010203_u32));
+    assert_eq!(field.sample(&[0xFF; 16]).value, BigUint::from(u128::MAX) % get_field_prime());
+    assert_eq!(Field::new(BigUint::from(256_u16)).byte_len(), 2);
+  }
+
+  #[test]
+  fn cube_root_of_unity () {
+    assert_eq!(Field::new(BigUint::from(11_u8)).cube_root_of_unity(), None);
+
+    let field = Field::new(BigUint::from(61_u8));
+    let w = field.cube_root_of_unity().unwrap();
+    assert!(!w.is_one());
+    assert!((w.clone() * w.clone() * w.clone()).is_one());
+    assert!((w.clone() * w.clone() + w + field.one()).is_zero());
+  }
 }
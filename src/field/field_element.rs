@@ -1,7 +1,8 @@
-use std::fmt::{Debug, Formatter};
+use std::fmt::{Debug, Display, Formatter};
 use std::ops::{Add, BitXor, Div, Mul, Neg, Sub};
 use num_bigint::BigUint;
 use num_traits::{One, Zero};
+use crate::error::Error;
 use crate::field::field::Field;
 
 #[derive(Clone, PartialEq, PartialOrd)]
@@ -18,10 +19,16 @@ impl Debug for FieldElement<'_> {
 
 impl<'a> FieldElement<'a> {
   pub fn unstringify(str: &str, field: &'a Field) -> Self {
-    Self {
-      field,
-      value: str.parse().unwrap(),
+    Self::try_unstringify(str, field).expect("should be a decimal number below the field order")
+  }
+
+  // decimal digits only, value has to be below `p`
+  pub fn try_unstringify(str: &str, field: &'a Field) -> Result<Self, Error> {
+    if str.is_empty() || !str.bytes().all(|c| c.is_ascii_digit()) {
+      return Err(Error::InvalidEncoding);
     }
+    let value = BigUint::parse_bytes(str.as_bytes(), 10).ok_or(Error::InvalidEncoding)?;
+    Self::canonical(field, value)
   }
 
   pub fn new(field: &'a Field, value: BigUint) -> Self {
@@ -31,11 +38,89 @@ impl<'a> FieldElement<'a> {
     }
   }
 
+  // big-endian, padded to the byte length of the field prime
+  pub fn to_bytes_be(&self) -> Vec<u8> {
+    let value = self.value.to_bytes_be();
+    let mut bytes = vec![0; self.field.byte_len() - value.len()];
+    bytes.extend_from_slice(&value);
+    bytes
+  }
+
+  pub fn to_bytes_le(&self) -> Vec<u8> {
+    let mut bytes = self.to_bytes_be();
+    bytes.reverse();
+    bytes
+  }
+
+  // inverse of `to_bytes_be`, rejects wrong length and values that are not below `p`
+  pub fn from_bytes_be(field: &'a Field, bytes: &[u8]) -> Result<Self, Error> {
+    if bytes.len() != field.byte_len() {
+      return Err(Error::InvalidEncoding);
+    }
+    Self::canonical(field, BigUint::from_bytes_be(bytes))
+  }
+
+  pub fn from_bytes_le(field: &'a Field, bytes: &[u8]) -> Result<Self, Error> {
+    if bytes.len() != field.byte_len() {
+      return Err(Error::InvalidEncoding);
+    }
+    Self::canonical(field, BigUint::from_bytes_le(bytes))
+  }
+
+  // lowercase big-endian hex of `to_bytes_be`, two digits per byte
+  pub fn to_hex(&self) -> String {
+    self.to_bytes_be().iter().map(|b| format!("{:02x}", b)).collect()
+  }
+
+  // big-endian hex with an optional `0x` prefix, any number of digits in either case,
+  // the value has to be below `p`
+  pub fn from_hex(field: &'a Field, str: &str) -> Result<Self, Error> {
+    let digits = str.strip_prefix("0x").unwrap_or(str);
+    if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_hexdigit()) {
+      return Err(Error::InvalidEncoding);
+    }
+    let value = BigUint::parse_bytes(digits.as_bytes(), 16).ok_or(Error::InvalidEncoding)?;
+    Self::canonical(field, value)
+  }
+
+  fn canonical(field: &'a Field, value: BigUint) -> Result<Self, Error> {
+    if value >= field.order {
+      return Err(Error::InvalidEncoding);
+    }
+    Ok(Self::new(field, value))
+  }
+
+  // `self / rhs` that fails instead of panicking when `rhs` is zero
+  pub fn try_div(self, rhs: Self) -> Result<Self, Error> {
+    if rhs.is_zero() {
+      return Err(Error::DivisionByZero);
+    }
+    Ok(self * rhs.inverse())
+  }
+
   pub fn inverse(mut self) -> Self {
     self.value = self.field.inv(self.value);
     self
   }
 
+  // returns `(b, a)` if `choice` is set and `(a, b)` otherwise. Both values are padded to the limb
+  // count of `p` and swapped with a xor mask, so the same instructions run for either choice.
+  // `BigUint` arithmetic elsewhere is still variable time
+  pub fn conditional_swap(choice: bool, a: Self, b: Self) -> (Self, Self) {
+    let limbs = (a.field.order.bits() as usize).div_ceil(32);
+    let mask = 0_u32.wrapping_sub(choice as u32);
+    let mut x = a.value.to_u32_digits();
+    let mut y = b.value.to_u32_digits();
+    x.resize(limbs, 0);
+    y.resize(limbs, 0);
+    for (x, y) in x.iter_mut().zip(y.iter_mut()) {
+      let t = mask & (*x ^ *y);
+      *x ^= t;
+      *y ^= t;
+    }
+    (Self::new(a.field, BigUint::new(x)), Self::new(b.field, BigUint::new(y)))
+  }
+
   pub fn is_zero(&self) -> bool {
     self.value.is_zero()
   }
@@ -43,6 +128,15 @@ impl<'a> FieldElement<'a> {
   pub fn is_one(&self) -> bool {
     self.value.is_one()
   }
+
+  // Euler's criterion, zero is considered a square
+  pub fn is_square(&self) -> bool {
+    if self.is_zero() {
+      return true;
+    }
+    let exp = (self.field.order.clone() - BigUint::one()) / BigUint::from(2_u8);
+    (self.clone() ^ exp).is_one()
+  }
 }
 
 impl<'a> Add for FieldElement<'a> {
@@ -72,9 +166,7 @@ impl<'a> Mul for FieldElement<'a> {
 impl<'a> Div for FieldElement<'a> {
   type Output = FieldElement<'a>;
   fn div (self, rhs: Self) -> Self::Output {
-    assert_ne!(rhs.value, BigUint::zero(), "divide by zero");
-
-    self * rhs.inverse()
+    self.try_div(rhs).expect("divide by zero")
   }
 }
 
@@ -86,9 +178,9 @@ impl<'a> Neg for FieldElement<'a> {
   }
 }
 
-impl<'a> ToString for FieldElement<'a> {
-  fn to_string (&self) -> String {
-    self.value.to_string()
+impl Display for FieldElement<'_> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.value)
   }
 }
 
@@ -123,6 +215,15 @@ mod tests {
     assert_eq!(el_1 * el_3, FieldElement::new(&field, BigUint::from(270497897142230380135924736767050121211_u128)));
   }
 
+  #[test]
+  fn conditional_swap () {
+    let field = Field::new(get_field_prime());
+    let a = FieldElement::new(&field, BigUint::from(u128::MAX >> 8));
+    let b = FieldElement::new(&field, BigUint::from(5_u8));
+    assert_eq!(FieldElement::conditional_swap(false, a.clone(), b.clone()), (a.clone(), b.clone()));
+    assert_eq!(FieldElement::conditional_swap(true, a.clone(), b.clone()), (b, a));
+  }
+
   #[test]
   fn div () {
     let field = Field::new(get_field_prime());
@@ -155,6 +256,11 @@ mod tests {
       &field,
       BigUint::from(6_u8), // because 6 * 7 = 2 (mod 8)
     ));
+
+    let field = Field::new(get_field_prime());
+    let el = FieldElement::new(&field, BigUint::from(12_u8));
+    assert_eq!(el.clone().try_div(FieldElement::new(&field, BigUint::from(4_u8))), Ok(FieldElement::new(&field, BigUint::from(3_u8))));
+    assert_eq!(el.try_div(field.zero()), Err(Error::DivisionByZero));
   }
 
   #[test]
@@ -235,6 +341,60 @@ mod tests {
     assert_eq!(el ^ BigUint::from(8_usize), FieldElement::new(&field, BigUint::from(79016866124691016201920330826259043252_u128)));
   }
 
+  #[test]
+  fn encoding () {
+    let field = Field::new(get_field_prime());
+    let el = FieldElement::new(&field, BigUint::from(0x0102_u16));
+    let be = el.to_bytes_be();
+    assert_eq!(be.len(), 16);
+    assert_eq!(&be[14..], &[1, 2]);
+    assert_eq!(&el.to_bytes_le()[..3], &[2, 1, 0]);
+    assert_eq!(FieldElement::from_bytes_be(&field, &be), Ok(el.clone()));
+    assert_eq!(FieldElement::from_bytes_le(&field, &el.to_bytes_le()), Ok(el.clone()));
+
+    assert_eq!(FieldElement::from_bytes_be(&field, &be[1..]), Err(Error::InvalidEncoding));
+    assert_eq!(FieldElement::from_bytes_le(&field, &[be.clone(), vec![0]].concat()), Err(Error::InvalidEncoding));
+    // `p` itself and `p + 1` are congruent to valid elements but not canonical
+    let p = get_field_prime().to_bytes_be();
+    assert_eq!(FieldElement::from_bytes_be(&field, &p), Err(Error::InvalidEncoding));
+    let mut p1 = get_field_prime().to_bytes_le();
+    p1[0] += 1;
+    assert_eq!(FieldElement::from_bytes_le(&field, &p1), Err(Error::InvalidEncoding));
+
+    let max = field.zero() - field.one();
+    assert_eq!(FieldElement::from_bytes_be(&field, &max.to_bytes_be()), Ok(max));
+  }
+
+  #[test]
+  fn hex () {
+    let field = Field::new(get_field_prime());
+    let el = FieldElement::new(&field, BigUint::from(0xABCDEF_u32));
+    assert_eq!(el.to_hex(), "00000000000000000000000000abcdef");
+    assert_eq!(FieldElement::from_hex(&field, &el.to_hex()), Ok(el.clone()));
+    assert_eq!(FieldElement::from_hex(&field, "0xABCDEF"), Ok(el));
+    assert_eq!(FieldElement::from_hex(&field, "0"), Ok(field.zero()));
+
+    assert_eq!(FieldElement::from_hex(&field, ""), Err(Error::InvalidEncoding));
+    assert_eq!(FieldElement::from_hex(&field, "0x"), Err(Error::InvalidEncoding));
+    assert_eq!(FieldElement::from_hex(&field, "12g4"), Err(Error::InvalidEncoding));
+    assert_eq!(FieldElement::from_hex(&field, "+123"), Err(Error::InvalidEncoding));
+    assert_eq!(FieldElement::from_hex(&field, "_123"), Err(Error::InvalidEncoding));
+    assert_eq!(FieldElement::from_hex(&field, &format!("{:x}", get_field_prime())), Err(Error::InvalidEncoding));
+  }
+
+  #[test]
+  fn unstringify () {
+    let field = Field::new(get_field_prime());
+    assert_eq!(FieldElement::unstringify("11259375", &field), FieldElement::new(&field, BigUint::from(0xABCDEF_u32)));
+    assert_eq!(FieldElement::try_unstringify("0", &field), Ok(field.zero()));
+
+    assert_eq!(FieldElement::try_unstringify("", &field), Err(Error::InvalidEncoding));
+    assert_eq!(FieldElement::try_unstringify("-1", &field), Err(Error::InvalidEncoding));
+    assert_eq!(FieldElement::try_unstringify("0x12", &field), Err(Error::InvalidEncoding));
+    assert_eq!(FieldElement::try_unstringify("1_000", &field), Err(Error::InvalidEncoding));
+    assert_eq!(FieldElement::try_unstringify(&get_field_prime().to_string(), &field), Err(Error::InvalidEncoding));
+  }
+
   // fn bitxor () {
   //   let field = Field::new(FIELD_PRIME);
   //
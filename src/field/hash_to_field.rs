@@ -0,0 +1,226 @@
+use num_bigint::BigUint;
+use sha2::{Sha256, Sha512};
+use sha2::digest::{Digest, ExtendableOutput, Update, XofReader};
+use sha2::digest::core_api::BlockSizeUser;
+use sha3::{Shake128, Shake256};
+use crate::error::Error;
+use crate::field::field::Field;
+use crate::field::field_element::FieldElement;
+
+// https://www.rfc-editor.org/rfc/rfc9380.html, section 5
+
+// tags longer than 255 bytes are hashed with this prefix, section 5.3.3
+const OVERSIZE_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+
+// `expand_message` variants of the suites in section 8
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Expander {
+  Sha256,
+  Sha512,
+  Shake128,
+  Shake256,
+}
+
+impl Expander {
+  pub fn expand(&self, msg: &[u8], dst: &[u8], len: usize) -> Result<Vec<u8>, Error> {
+    match self {
+      Expander::Sha256 => expand_message_xmd::<Sha256>(msg, dst, len),
+      Expander::Sha512 => expand_message_xmd::<Sha512>(msg, dst, len),
+      Expander::Shake128 => expand_message_xof::<Shake128>(msg, dst, len, 128),
+      Expander::Shake256 => expand_message_xof::<Shake256>(msg, dst, len, 256),
+    }
+  }
+}
+
+// `len` uniform bytes out of a Merkle-Damgard hash, at most `255` blocks of output
+pub fn expand_message_xmd<H: Digest + BlockSizeUser>(
+  msg: &[u8],
+  dst: &[u8],
+  len: usize,
+) -> Result<Vec<u8>, Error> {
+  let b_len = <H as Digest>::output_size();
+  let ell = len.div_ceil(b_len);
+  if dst.is_empty() || ell > 255 || len > u16::MAX as usize {
+    return Err(Error::InvalidExpansion);
+  }
+  let dst = if dst.len() > 255 {
+    H::new().chain_update(OVERSIZE_DST_PREFIX).chain_update(dst).finalize().to_vec()
+  } else {
+    dst.to_vec()
+  };
+  let dst_prime = [dst.as_slice(), &[dst.len() as u8]].concat();
+
+  let b_0 = H::new()
+    .chain_update(vec![0; H::block_size()])
+    .chain_update(msg)
+    .chain_update((len as u16).to_be_bytes())
+    .chain_update([0])
+    .chain_update(&dst_prime)
+    .finalize();
+  let mut b_i = H::new()
+    .chain_update(&b_0)
+    .chain_update([1])
+    .chain_update(&dst_prime)
+    .finalize();
+
+  let mut bytes = b_i.to_vec();
+  for i in 2..=ell {
+    let xor: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+    b_i = H::new()
+      .chain_update(xor)
+      .chain_update([i as u8])
+      .chain_update(&dst_prime)
+      .finalize();
+    bytes.extend_from_slice(&b_i);
+  }
+  bytes.truncate(len);
+  Ok(bytes)
+}
+
+// `len` uniform bytes out of an extendable-output function with `k`-bit security
+pub fn expand_message_xof<H: Default + Update + ExtendableOutput>(
+  msg: &[u8],
+  dst: &[u8],
+  len: usize,
+  k: usize,
+) -> Result<Vec<u8>, Error> {
+  if dst.is_empty() || len > u16::MAX as usize {
+    return Err(Error::InvalidExpansion);
+  }
+  let dst = if dst.len() > 255 {
+    let mut h = H::default();
+    h.update(OVERSIZE_DST_PREFIX);
+    h.update(dst);
+    let mut short = vec![0; (2 * k).div_ceil(8)];
+    h.finalize_xof().read(&mut short);
+    short
+  } else {
+    dst.to_vec()
+  };
+
+  let mut h = H::default();
+  h.update(msg);
+  h.update(&(len as u16).to_be_bytes());
+  h.update(&dst);
+  h.update(&[dst.len() as u8]);
+  let mut bytes = vec![0; len];
+  h.finalize_xof().read(&mut bytes);
+  Ok(bytes)
+}
+
+// `count` elements of a prime field, each reduced from `ceil((bits(p) + k) / 8)` bytes
+// so that the bias is below `2^-k`, unlike `Field::sample`
+pub fn hash_to_field<'a>(
+  field: &'a Field,
+  msg: &[u8],
+  dst: &[u8],
+  count: usize,
+  expander: Expander,
+  k: usize,
+) -> Result<Vec<FieldElement<'a>>, Error> {
+  let l = (field.order.bits() as usize + k).div_ceil(8);
+  let bytes = expander.expand(msg, dst, count * l)?;
+  Ok(bytes
+    .chunks(l)
+    .map(|chunk| field.get(BigUint::from_bytes_be(chunk)))
+    .collect())
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::curves::named::{P256, SECP256K1};
+  use crate::test_utils::hex_bytes;
+  use super::*;
+
+  // appendix K.1
+  #[test]
+  fn xmd_sha256 () {
+    let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+    assert_eq!(
+      expand_message_xmd::<Sha256>(b"", dst, 0x20),
+      Ok(hex_bytes("68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235")),
+    );
+    assert_eq!(
+      expand_message_xmd::<Sha256>(b"abcdef0123456789", dst, 0x20),
+      Ok(hex_bytes("eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1")),
+    );
+    assert_eq!(
+      expand_message_xmd::<Sha256>(b"abc", dst, 0x80),
+      Ok(hex_bytes("abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40")),
+    );
+
+    let long = [b"QUUX-V01-CS02-with-expander-SHA256-128-long-DST-".as_slice(), &[b'1'; 208]].concat();
+    assert_eq!(
+      expand_message_xmd::<Sha256>(b"", &long, 0x20),
+      Ok(hex_bytes("e8dc0c8b686b7ef2074086fbdd2f30e3f8bfbd3bdf177f73f04b97ce618a3ed3")),
+    );
+  }
+
+  // appendix K.2
+  #[test]
+  fn xmd_sha512 () {
+    let dst = b"QUUX-V01-CS02-with-expander-SHA512-256";
+    assert_eq!(
+      Expander::Sha512.expand(b"", dst, 0x20),
+      Ok(hex_bytes("6b9a7312411d92f921c6f68ca0b6380730a1a4d982c507211a90964c394179ba")),
+    );
+    assert_eq!(
+      Expander::Sha512.expand(b"abc", dst, 0x20),
+      Ok(hex_bytes("0da749f12fbe5483eb066a5f595055679b976e93abe9be6f0f6318bce7aca8dc")),
+    );
+  }
+
+  // appendix K.3 and K.5
+  #[test]
+  fn xof_shake () {
+    let dst = b"QUUX-V01-CS02-with-expander-SHAKE128";
+    assert_eq!(
+      Expander::Shake128.expand(b"", dst, 0x20),
+      Ok(hex_bytes("86518c9cd86581486e9485aa74ab35ba150d1c75c88e26b7043e44e2acd735a2")),
+    );
+    assert_eq!(
+      Expander::Shake128.expand(b"abc", dst, 0x80),
+      Ok(hex_bytes("c952f0c8e529ca8824acc6a4cab0e782fc3648c563ddb00da7399f2ae35654f4860ec671db2356ba7baa55a34a9d7f79197b60ddae6e64768a37d699a78323496db3878c8d64d909d0f8a7de4927dcab0d3dbbc26cb20a49eceb0530b431cdf47bc8c0fa3e0d88f53b318b6739fbed7d7634974f1b5c386d6230c76260d5337a")),
+    );
+
+    let long = [b"QUUX-V01-CS02-with-expander-SHAKE128-long-DST-".as_slice(), &[b'1'; 210]].concat();
+    assert_eq!(
+      Expander::Shake128.expand(b"", &long, 0x20),
+      Ok(hex_bytes("827c6216330a122352312bccc0c8d6e7a146c5257a776dbd9ad9d75cd880fc53")),
+    );
+
+    let dst = b"QUUX-V01-CS02-with-expander-SHAKE256";
+    assert_eq!(
+      Expander::Shake256.expand(b"abc", dst, 0x20),
+      Ok(hex_bytes("b39e493867e2767216792abce1f2676c197c0692aed061560ead251821808e07")),
+    );
+  }
+
+  #[test]
+  fn limits () {
+    // 255 blocks of SHA-256
+    assert_eq!(Expander::Sha256.expand(b"", b"DST", 255 * 32).map(|b| b.len()), Ok(255 * 32));
+    assert_eq!(Expander::Sha256.expand(b"", b"DST", 255 * 32 + 1), Err(Error::InvalidExpansion));
+    assert_eq!(Expander::Shake128.expand(b"", b"DST", 1 << 16), Err(Error::InvalidExpansion));
+    assert_eq!(Expander::Sha512.expand(b"", b"", 32), Err(Error::InvalidExpansion));
+    assert_eq!(Expander::Shake256.expand(b"", b"", 32), Err(Error::InvalidExpansion));
+  }
+
+  // `u` values of the `_RO_` suites in appendix J.1.1 and J.8.1
+  #[test]
+  fn field_elements () {
+    let field = P256.field();
+    let u = hash_to_field(&field, b"abc", b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_", 2, Expander::Sha256, 128).unwrap();
+    assert_eq!(u, vec![
+      FieldElement::from_hex(&field, "afe47f2ea2b10465cc26ac403194dfb68b7f5ee865cda61e9f3e07a537220af1").unwrap(),
+      FieldElement::from_hex(&field, "379a27833b0bfe6f7bdca08e1e83c760bf9a338ab335542704edcd69ce9e46e0").unwrap(),
+    ]);
+
+    let field = SECP256K1.field();
+    let u = hash_to_field(&field, b"", b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_", 2, Expander::Sha256, 128).unwrap();
+    assert_eq!(u, vec![
+      FieldElement::from_hex(&field, "6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3").unwrap(),
+      FieldElement::from_hex(&field, "1ae6c212e08fe1a5937f6202f929a2cc8ef4ee5b9782db68b0d5799fd8f09e16").unwrap(),
+    ]);
+  }
+}
@@ -1,2 +1,5 @@
+#[allow(clippy::module_inception)]
 pub mod field;
 pub mod field_element;
+pub mod hash_to_field;
+pub mod scalar;
@@ -0,0 +1,213 @@
+use std::ops::{Add, Mul, Neg, Sub};
+use num_bigint::BigUint;
+use rand::{CryptoRng, RngCore, thread_rng};
+use crate::error::Error;
+use crate::field::field::Field;
+use crate::field::field_element::FieldElement;
+
+// integer modulo the prime order `n` of a curve subgroup, `field` is `Field::new(n)`,
+// see `WeierstrassCurve::scalar_field`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scalar<'a> {
+  element: FieldElement<'a>,
+}
+
+impl<'a> Scalar<'a> {
+  // reduces `value` modulo `n`
+  pub fn new(field: &'a Field, value: BigUint) -> Self {
+    Self {
+      element: field.get(value),
+    }
+  }
+
+  pub fn zero(field: &'a Field) -> Self {
+    Self {
+      element: field.zero(),
+    }
+  }
+
+  pub fn one(field: &'a Field) -> Self {
+    Self {
+      element: field.one(),
+    }
+  }
+
+  pub fn value(&self) -> &BigUint {
+    &self.element.value
+  }
+
+  pub fn field(&self) -> &'a Field {
+    self.element.field
+  }
+
+  pub fn is_zero(&self) -> bool {
+    self.element.is_zero()
+  }
+
+  // `k^-1 mod n`, zero has no inverse
+  pub fn invert(&self) -> Option<Self> {
+    if self.is_zero() {
+      return None;
+    }
+    Some(Self {
+      element: self.element.clone().inverse(),
+    })
+  }
+
+  pub fn random(field: &'a Field) -> Self {
+    Self::random_with(field, &mut thread_rng())
+  }
+
+  // uniform in `[0, n)`, samples `bits(n)` random bits until the value is below `n`,
+  // which takes less than 2 attempts on average
+  pub fn random_with<R: RngCore + CryptoRng>(field: &'a Field, rng: &mut R) -> Self {
+    let bits = field.order.bits();
+    let mut bytes = vec![0; field.byte_len()];
+    loop {
+      rng.fill_bytes(&mut bytes);
+      if !bits.is_multiple_of(8) {
+        bytes[0] &= (1 << (bits % 8)) - 1;
+      }
+      let value = BigUint::from_bytes_be(&bytes);
+      if value < field.order {
+        return Self::new(field, value);
+      }
+    }
+  }
+
+  // big-endian 512-bit integer reduced modulo `n`, e.g. output of SHA-512.
+  // The bias is below `2^(bits(n) - 512)`, negligible for orders up to 384 bits
+  pub fn from_bytes_wide(field: &'a Field, bytes: &[u8; 64]) -> Self {
+    Self::new(field, BigUint::from_bytes_be(bytes))
+  }
+
+  // big-endian, padded to the byte length of `n`
+  pub fn to_bytes(&self) -> Vec<u8> {
+    self.element.to_bytes_be()
+  }
+
+  // inverse of `to_bytes`, rejects wrong length and values that are not reduced modulo `n`
+  pub fn from_bytes(field: &'a Field, bytes: &[u8]) -> Result<Self, Error> {
+    Ok(Self {
+      element: FieldElement::from_bytes_be(field, bytes)?,
+    })
+  }
+}
+
+impl<'a> Add for Scalar<'a> {
+  type Output = Self;
+  fn add (self, rhs: Self) -> Self::Output {
+    Self {
+      element: self.element + rhs.element,
+    }
+  }
+}
+
+impl<'a> Sub for Scalar<'a> {
+  type Output = Self;
+  fn sub (self, rhs: Self) -> Self::Output {
+    Self {
+      element: self.element - rhs.element,
+    }
+  }
+}
+
+impl<'a> Mul for Scalar<'a> {
+  type Output = Self;
+  fn mul (self, rhs: Self) -> Self::Output {
+    Self {
+      element: self.element * rhs.element,
+    }
+  }
+}
+
+impl<'a> Neg for Scalar<'a> {
+  type Output = Self;
+  fn neg (self) -> Self::Output {
+    Self {
+      element: -self.element,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use num_bigint::BigUint;
+  use rand::SeedableRng;
+  use rand::rngs::StdRng;
+  use crate::curves::named::{P256, P521};
+  use crate::curves::weierstass::WeierstrassCurve;
+  use crate::error::Error;
+  use super::*;
+
+  #[test]
+  fn arithmetic () {
+    let field = Field::new(P256.order());
+    let a = Scalar::new(&field, P256.order() + BigUint::from(5_u8));
+    assert_eq!(a.value(), &BigUint::from(5_u8));
+
+    let b = Scalar::new(&field, BigUint::from(7_u8));
+    assert_eq!((a.clone() - b.clone()).value(), &(P256.order() - BigUint::from(2_u8)));
+    assert!((a.clone() + -a.clone()).is_zero());
+    assert_eq!(a.invert().unwrap() * a.clone(), Scalar::one(&field));
+    assert_eq!(Scalar::zero(&field).invert(), None);
+  }
+
+  #[test]
+  fn curve_order () {
+    let field = P256.field();
+    let e = P256.curve(&field);
+    let scalars = e.scalar_field().unwrap();
+    let k = Scalar::new(&scalars, BigUint::from(12345_u16));
+    let g = e.get_base();
+    let sum = e.point_add(e.wnaf_mul(k.value().clone(), g.clone()), e.wnaf_mul((-k).value().clone(), g));
+    assert!(sum.is_infinity());
+
+    let e = WeierstrassCurve::new(field.one(), field.one());
+    assert_eq!(e.scalar_field().err(), Some(Error::UnknownOrder));
+  }
+
+  #[test]
+  fn encoding () {
+    let field = Field::new(P256.order());
+    let k = Scalar::new(&field, BigUint::from(0x0102_u16));
+    let bytes = k.to_bytes();
+    assert_eq!(bytes.len(), 32);
+    assert_eq!(&bytes[30..], &[1, 2]);
+    assert_eq!(Scalar::from_bytes(&field, &bytes), Ok(k));
+
+    assert_eq!(Scalar::from_bytes(&field, &bytes[1..]), Err(Error::InvalidEncoding));
+    assert_eq!(Scalar::from_bytes(&field, &P256.order().to_bytes_be()), Err(Error::InvalidEncoding));
+
+    // 521-bit order takes 66 bytes
+    let field = Field::new(P521.order());
+    assert_eq!(Scalar::one(&field).to_bytes().len(), 66);
+  }
+
+  #[test]
+  fn wide_reduction () {
+    let field = Field::new(P256.order());
+    let k = Scalar::from_bytes_wide(&field, &[0xFF; 64]);
+    let expected = ((BigUint::from(1_u8) << 512) - BigUint::from(1_u8)) % P256.order();
+    assert_eq!(k.value(), &expected);
+  }
+
+  #[test]
+  fn random () {
+    let field = Field::new(BigUint::from(13_u8));
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut seen = [false; 13];
+    for _ in 0..500 {
+      let k = Scalar::random_with(&field, &mut rng);
+      seen[u8::try_from(k.value()).unwrap() as usize] = true;
+    }
+    assert!(seen.iter().all(|s| *s));
+
+    let field = Field::new(P521.order());
+    assert!(Scalar::random(&field).value() < &P521.order());
+    assert_eq!(
+      Scalar::random_with(&field, &mut StdRng::seed_from_u64(7)),
+      Scalar::random_with(&field, &mut StdRng::seed_from_u64(7)),
+    );
+  }
+}
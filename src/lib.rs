@@ -1,7 +1,12 @@
 
+pub mod error;
 pub mod field;
 pub mod utils;
 pub mod curves;
+pub mod protocols;
+
+#[cfg(test)]
+mod test_utils;
 
 
 #[cfg(test)]
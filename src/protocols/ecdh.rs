@@ -0,0 +1,159 @@
+use num_bigint::BigUint;
+use crate::curves::edwards::ed25519_prime;
+use crate::curves::montgomery::MontgomeryCurve;
+use crate::field::field::Field;
+
+// https://www.rfc-editor.org/rfc/rfc7748
+
+pub const X25519_BASE_POINT: [u8; 32] = {
+    let mut u = [0; 32];
+    u[0] = 9;
+    u
+};
+
+pub const X448_BASE_POINT: [u8; 56] = {
+    let mut u = [0; 56];
+    u[0] = 5;
+    u
+};
+
+// p = 2^448 - 2^224 - 1
+fn curve448_prime() -> BigUint {
+    (BigUint::from(1_u8) << 448) - (BigUint::from(1_u8) << 224) - BigUint::from(1_u8)
+}
+
+// clears 3 lowest bits so the scalar is a multiple of cofactor 8,
+// sets bit 254 so the ladder always runs in the same number of steps
+pub fn clamp_x25519(mut k: [u8; 32]) -> [u8; 32] {
+    k[0] &= 248;
+    k[31] &= 127;
+    k[31] |= 64;
+    k
+}
+
+// clears 2 lowest bits (cofactor 4), sets bit 447
+pub fn clamp_x448(mut k: [u8; 56]) -> [u8; 56] {
+    k[0] &= 252;
+    k[55] |= 128;
+    k
+}
+
+// Caller should reject an all-zero result if contributory behaviour is required,
+// it is produced for low order `u`
+pub fn x25519(k: [u8; 32], u: [u8; 32]) -> [u8; 32] {
+    let field = Field::new(ed25519_prime());
+    let curve = MontgomeryCurve::new(field.get(BigUint::from(486662_u32)), field.one());
+
+    let k = BigUint::from_bytes_le(&clamp_x25519(k));
+    // most significant bit is ignored, non-canonical values are reduced
+    let mut u = u;
+    u[31] &= 127;
+    let u = field.get(BigUint::from_bytes_le(&u));
+
+    let x = curve.ladder(&k, 255, u);
+    x.to_bytes_le().try_into().expect("field of the curve should match the key length")
+}
+
+pub fn x448(k: [u8; 56], u: [u8; 56]) -> [u8; 56] {
+    let field = Field::new(curve448_prime());
+    let curve = MontgomeryCurve::new(field.get(BigUint::from(156326_u32)), field.one());
+
+    let k = BigUint::from_bytes_le(&clamp_x448(k));
+    let u = field.get(BigUint::from_bytes_le(&u));
+
+    let x = curve.ladder(&k, 448, u);
+    x.to_bytes_le().try_into().expect("field of the curve should match the key length")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::hex_bytes;
+    use super::*;
+
+    // fixed-size key from hex
+    fn hex<const N: usize>(s: &str) -> [u8; N] {
+        hex_bytes(s).try_into().unwrap()
+    }
+
+    #[test]
+    fn x25519_vectors() {
+        // rfc7748 5.2
+        assert_eq!(
+            x25519(
+                hex("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4"),
+                hex("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c"),
+            ),
+            hex("c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552"),
+        );
+        assert_eq!(
+            x25519(
+                hex("4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d"),
+                hex("e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493"),
+            ),
+            hex("95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957"),
+        );
+    }
+
+    #[test]
+    fn x25519_diffie_hellman() {
+        // rfc7748 6.1
+        let alice = hex("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a");
+        let bob = hex("5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb");
+
+        let alice_public = x25519(alice, X25519_BASE_POINT);
+        let bob_public = x25519(bob, X25519_BASE_POINT);
+        assert_eq!(alice_public, hex("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a"));
+        assert_eq!(bob_public, hex("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f"));
+
+        let shared = hex("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+        assert_eq!(x25519(alice, bob_public), shared);
+        assert_eq!(x25519(bob, alice_public), shared);
+    }
+
+    #[test]
+    fn x448_vectors() {
+        // rfc7748 5.2
+        assert_eq!(
+            x448(
+                hex("3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121700a779c984c24f8cdd78fbff44943eba368f54b29259a4f1c600ad3"),
+                hex("06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9814dc031ddbdc38c19c6da2583fa5429db94ada18aa7a7fb4ef8a086"),
+            ),
+            hex("ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239fe14fbaadeb445fc66a01b0779d98223961111e21766282f73dd96b6f"),
+        );
+        assert_eq!(
+            x448(
+                hex("203d494428b8399352665ddca42f9de8fef600908e0d461cb021f8c538345dd77c3e4806e25f46d3315c44e0a5b4371282dd2c8d5be3095f"),
+                hex("0fbcc2f993cd56d3305b0b7d9e55d4c1a8fb5dbb52f8e9a1e9b6201b165d015894e56c4d3570bee52fe205e28a78b91cdfbde71ce8d157db"),
+            ),
+            hex("884a02576239ff7a2f2f63b2db6a9ff37047ac13568e1e30fe63c4a7ad1b3ee3a5700df34321d62077e63633c575c1c954514e99da7c179d"),
+        );
+    }
+
+    // rfc7748 5.2, `k, u = x25519(k, u), k` starting with `k = u = 9`.
+    // `num-bigint` is optimized in the dev profile, so 1000 iterations take seconds
+    #[test]
+    fn x25519_iterated() {
+        let mut k = X25519_BASE_POINT;
+        let mut u = X25519_BASE_POINT;
+        for i in 1..=1000 {
+            (k, u) = (x25519(k, u), k);
+            if i == 1 {
+                assert_eq!(k, hex("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079"));
+            }
+        }
+        assert_eq!(k, hex("684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51"));
+    }
+
+    #[test]
+    fn x448_iterated() {
+        let mut k = X448_BASE_POINT;
+        let mut u = X448_BASE_POINT;
+        for i in 1..=1000 {
+            (k, u) = (x448(k, u), k);
+            if i == 1 {
+                assert_eq!(k, hex("3f482c8a9f19b01e6c46ee9711d9dc14fd4bf67af30765c2ae2b846a4d23a8cd0db897086239492caf350b51f833868b9bc2b3bca9cf4113"));
+            }
+        }
+        assert_eq!(k, hex("aa3b4749d55b9daf1e5b00288826c467274ce3ebbdd5c17b975e09d4af6c67cf10d087202db88286e2b79fceea3ec353ef54faa26e219f38"));
+    }
+}
@@ -0,0 +1,764 @@
+use std::marker::PhantomData;
+use hmac::{Mac, SimpleHmac};
+use num_bigint::BigUint;
+use num_traits::Zero;
+use rand::{CryptoRng, RngCore, thread_rng};
+use sha2::digest::Digest;
+use sha2::digest::core_api::BlockSizeUser;
+use crate::curves::point::Point;
+use crate::curves::weierstass::WeierstrassCurve;
+use crate::error::Error;
+use crate::field::field::Field;
+use crate::field::scalar::Scalar;
+
+// https://www.secg.org/sec1-v2.pdf, section 4.1
+// deterministic nonces of https://www.rfc-editor.org/rfc/rfc6979
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub r: BigUint,
+    pub s: BigUint,
+}
+
+impl Signature {
+    // `(r, s)` and `(r, n - s)` are both valid, the one with `s <= n/2` is used where malleability matters
+    pub fn is_low_s(&self, scalars: &Field) -> bool {
+        self.s <= &scalars.order >> 1
+    }
+
+    pub fn normalize_s(&self, scalars: &Field) -> Self {
+        if self.is_low_s(scalars) {
+            return self.clone();
+        }
+        Self {
+            r: self.r.clone(),
+            s: &scalars.order - &self.s,
+        }
+    }
+
+    // `r || s`, each big-endian and padded to the byte length of `n`
+    pub fn to_bytes(&self, scalars: &Field) -> Vec<u8> {
+        let mut bytes = Scalar::new(scalars, self.r.clone()).to_bytes();
+        bytes.extend(Scalar::new(scalars, self.s.clone()).to_bytes());
+        bytes
+    }
+
+    // inverse of `to_bytes`, rejects wrong length and halves that are not below `n`
+    pub fn from_bytes(scalars: &Field, bytes: &[u8]) -> Result<Self, Error> {
+        let len = scalars.byte_len();
+        if bytes.len() != 2 * len {
+            return Err(Error::InvalidEncoding);
+        }
+        let r = Scalar::from_bytes(scalars, &bytes[..len])?;
+        let s = Scalar::from_bytes(scalars, &bytes[len..])?;
+        Ok(Self {
+            r: r.value().clone(),
+            s: s.value().clone(),
+        })
+    }
+
+    // `SEQUENCE { INTEGER r, INTEGER s }`
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut body = der_integer(&self.r);
+        body.extend(der_integer(&self.s));
+        let mut bytes = vec![0x30];
+        bytes.extend(der_length(body.len()));
+        bytes.extend(body);
+        bytes
+    }
+
+    // strict DER, rejects non-minimal lengths and integers, negative values and trailing bytes
+    pub fn from_der(bytes: &[u8]) -> Result<Self, Error> {
+        let (body, rest) = der_read(bytes, 0x30)?;
+        if !rest.is_empty() {
+            return Err(Error::InvalidEncoding);
+        }
+        let (r, body) = der_read(body, 0x02)?;
+        let (s, body) = der_read(body, 0x02)?;
+        if !body.is_empty() {
+            return Err(Error::InvalidEncoding);
+        }
+        Ok(Self {
+            r: der_unsigned(r)?,
+            s: der_unsigned(s)?,
+        })
+    }
+}
+
+fn der_integer(v: &BigUint) -> Vec<u8> {
+    let mut value = v.to_bytes_be();
+    // positive integers get a leading zero when the top bit is set
+    if value[0] & 0x80 != 0 {
+        value.insert(0, 0);
+    }
+    let mut bytes = vec![0x02];
+    bytes.extend(der_length(value.len()));
+    bytes.extend(value);
+    bytes
+}
+
+fn der_length(len: usize) -> Vec<u8> {
+    if len < 0x80 {
+        return vec![len as u8];
+    }
+    let bytes: Vec<u8> = len.to_be_bytes().into_iter().skip_while(|b| *b == 0).collect();
+    let mut res = vec![0x80 | bytes.len() as u8];
+    res.extend(bytes);
+    res
+}
+
+// splits a `tag, length, value` element off the front of `bytes`
+fn der_read(bytes: &[u8], tag: u8) -> Result<(&[u8], &[u8]), Error> {
+    let (&t, bytes) = bytes.split_first().ok_or(Error::InvalidEncoding)?;
+    let (&first, mut bytes) = bytes.split_first().ok_or(Error::InvalidEncoding)?;
+    if t != tag {
+        return Err(Error::InvalidEncoding);
+    }
+    let len = if first < 0x80 {
+        first as usize
+    } else {
+        let count = (first & 0x7f) as usize;
+        if count == 0 || count > size_of::<usize>() || bytes.len() < count || bytes[0] == 0 {
+            return Err(Error::InvalidEncoding);
+        }
+        let len = bytes[..count].iter().fold(0, |acc, b| (acc << 8) | *b as usize);
+        // long form only for lengths that do not fit the short one
+        if len < 0x80 {
+            return Err(Error::InvalidEncoding);
+        }
+        bytes = &bytes[count..];
+        len
+    };
+    if bytes.len() < len {
+        return Err(Error::InvalidEncoding);
+    }
+    Ok(bytes.split_at(len))
+}
+
+fn der_unsigned(value: &[u8]) -> Result<BigUint, Error> {
+    match value {
+        [] => Err(Error::InvalidEncoding),
+        [b, ..] if b & 0x80 != 0 => Err(Error::InvalidEncoding),
+        [0, b, ..] if b & 0x80 == 0 => Err(Error::InvalidEncoding),
+        _ => Ok(BigUint::from_bytes_be(value)),
+    }
+}
+
+// leftmost `bits(n)` bits of a hash as an integer, RFC 6979 section 2.3.2
+fn bits2int(bytes: &[u8], n: &BigUint) -> BigUint {
+    let x = BigUint::from_bytes_be(bytes);
+    let len = bytes.len() as u64 * 8;
+    if len > n.bits() {
+        x >> (len - n.bits())
+    } else {
+        x
+    }
+}
+
+// HMAC_DRBG of RFC 6979 section 3.2 seeded with the secret key and the message hash,
+// yields candidates for `k` in `[1, n)`
+struct Rfc6979<D> {
+    k: Vec<u8>,
+    v: Vec<u8>,
+    _digest: PhantomData<D>,
+}
+
+impl<D: Digest + BlockSizeUser> Rfc6979<D> {
+    fn new(scalars: &Field, x: &BigUint, h1: &[u8]) -> Self {
+        let x = Scalar::new(scalars, x.clone()).to_bytes();
+        let h1 = Scalar::new(scalars, bits2int(h1, &scalars.order)).to_bytes();
+        let len = <D as Digest>::output_size();
+
+        let mut drbg = Self {
+            k: vec![0; len],
+            v: vec![1; len],
+            _digest: PhantomData,
+        };
+        for i in [0, 1] {
+            drbg.k = drbg.hmac(&[&drbg.v, &[i], &x, &h1]);
+            drbg.v = drbg.hmac(&[&drbg.v]);
+        }
+        drbg
+    }
+
+    fn next(&mut self, n: &BigUint) -> BigUint {
+        loop {
+            let mut t = vec![];
+            while (t.len() as u64 * 8) < n.bits() {
+                self.v = self.hmac(&[&self.v]);
+                t.extend_from_slice(&self.v);
+            }
+            let k = bits2int(&t, n);
+
+            // state is updated for every candidate, so a rejected signature moves on to a new `k`
+            self.k = self.hmac(&[&self.v, &[0]]);
+            self.v = self.hmac(&[&self.v]);
+            if !k.is_zero() && &k < n {
+                return k;
+            }
+        }
+    }
+
+    fn hmac(&self, data: &[&[u8]]) -> Vec<u8> {
+        let mut mac = <SimpleHmac<D> as Mac>::new_from_slice(&self.k).expect("HMAC takes keys of any length");
+        for d in data {
+            mac.update(d);
+        }
+        mac.finalize().into_bytes().to_vec()
+    }
+}
+
+fn generator<'a>(curve: &WeierstrassCurve<'a>) -> Result<Point<'a>, Error> {
+    curve.generator().cloned().ok_or(Error::UnknownGenerator)
+}
+
+fn secret<'a>(scalars: &'a Field, d: &BigUint) -> Result<Scalar<'a>, Error> {
+    if d.is_zero() || d >= &scalars.order {
+        return Err(Error::InvalidScalar);
+    }
+    Ok(Scalar::new(scalars, d.clone()))
+}
+
+// `Q = [d]G` in affine coordinates for a secret `d` in `[1, n)`,
+// uses `WeierstrassCurve::mul` which is not constant time and may leak `d` through timing
+pub fn public_key<'a>(curve: &WeierstrassCurve<'a>, d: &BigUint) -> Result<Point<'a>, Error> {
+    let scalars = curve.scalar_field()?;
+    let d = secret(&scalars, d)?;
+    Ok(curve.mul(d.value().clone(), generator(curve)?).to_affine().into())
+}
+
+pub fn generate_keypair<'a>(curve: &WeierstrassCurve<'a>) -> Result<(BigUint, Point<'a>), Error> {
+    generate_keypair_with(curve, &mut thread_rng())
+}
+
+pub fn generate_keypair_with<'a, R: RngCore + CryptoRng>(
+    curve: &WeierstrassCurve<'a>,
+    rng: &mut R,
+) -> Result<(BigUint, Point<'a>), Error> {
+    let scalars = curve.scalar_field()?;
+    loop {
+        let d = Scalar::random_with(&scalars, rng);
+        if !d.is_zero() {
+            let d = d.value().clone();
+            let q = public_key(curve, &d)?;
+            return Ok((d, q));
+        }
+    }
+}
+
+// `msg_hash` is `H(m)`, RFC 6979 nonces use HMAC with the digest `D` which is normally `H` itself.
+// Not constant time, `[k]G` and the scalar arithmetic on `BigUint` leak timing of `k` and `d`
+pub fn sign<D: Digest + BlockSizeUser>(
+    curve: &WeierstrassCurve,
+    d: &BigUint,
+    msg_hash: &[u8],
+) -> Result<Signature, Error> {
+    let scalars = curve.scalar_field()?;
+    secret(&scalars, d)?;
+    let mut nonces = Rfc6979::<D>::new(&scalars, d, msg_hash);
+    loop {
+        if let Some((sig, _)) = sign_with_nonce(curve, d, msg_hash, &nonces.next(&scalars.order))? {
+            return Ok(sig);
+        }
+    }
+}
+
+// low-S signature as required by Bitcoin and Ethereum, with the recovery id of `R = [k]G`
+// for `recover_public_key`: bit 0 is the parity of `y`, bit 1 is set when `x >= n`.
+// Leaks timing the same way `sign` does
+pub fn sign_recoverable<D: Digest + BlockSizeUser>(
+    curve: &WeierstrassCurve,
+    d: &BigUint,
+    msg_hash: &[u8],
+) -> Result<(Signature, u8), Error> {
+    let scalars = curve.scalar_field()?;
+    secret(&scalars, d)?;
+    let mut nonces = Rfc6979::<D>::new(&scalars, d, msg_hash);
+    loop {
+        if let Some((sig, id)) = sign_with_nonce(curve, d, msg_hash, &nonces.next(&scalars.order))? {
+            // `n - s` is the signature of `-R`, which has the other `y`
+            if sig.is_low_s(&scalars) {
+                return Ok((sig, id));
+            }
+            return Ok((sig.normalize_s(&scalars), id ^ 1));
+        }
+    }
+}
+
+// `r = x([k]G) mod n`, `s = k^-1 (z + rd) mod n` and the recovery id of `[k]G`,
+// `None` when `r` or `s` is zero and another `k` is needed
+fn sign_with_nonce(
+    curve: &WeierstrassCurve,
+    d: &BigUint,
+    msg_hash: &[u8],
+    k: &BigUint,
+) -> Result<Option<(Signature, u8)>, Error> {
+    let scalars = curve.scalar_field()?;
+    let d = secret(&scalars, d)?;
+    let k = Scalar::new(&scalars, k.clone());
+    let k_inv = k.invert().ok_or(Error::InvalidScalar)?;
+
+    let point = curve.mul(k.value().clone(), generator(curve)?).to_affine();
+    let id = point.y.value.bit(0) as u8 | ((point.x.value >= scalars.order) as u8) << 1;
+    let r = Scalar::new(&scalars, point.x.value);
+    let z = Scalar::new(&scalars, bits2int(msg_hash, &scalars.order));
+    let s = k_inv * (z + r.clone() * d);
+    if r.is_zero() || s.is_zero() {
+        return Ok(None);
+    }
+    let sig = Signature {
+        r: r.value().clone(),
+        s: s.value().clone(),
+    };
+    Ok(Some((sig, id)))
+}
+
+// accepts both `s` and `n - s`, see `Signature::normalize_s`
+pub fn verify<'a>(
+    curve: &WeierstrassCurve<'a>,
+    q: &Point<'a>,
+    msg_hash: &[u8],
+    sig: &Signature,
+) -> Result<(), Error> {
+    let scalars = curve.scalar_field()?;
+    let g = generator(curve)?;
+    curve.validate(q)?;
+    if sig.r.is_zero() || sig.s.is_zero() || sig.r >= scalars.order || sig.s >= scalars.order {
+        return Err(Error::InvalidSignature);
+    }
+
+    let z = Scalar::new(&scalars, bits2int(msg_hash, &scalars.order));
+    let w = Scalar::new(&scalars, sig.s.clone()).invert().ok_or(Error::InvalidSignature)?;
+    let u1 = z * w.clone();
+    let u2 = Scalar::new(&scalars, sig.r.clone()) * w;
+
+    // `[u1]G + [u2]Q` with shared doublings
+    let point = curve
+        .multi_scalar_mul(&[(u1.value().clone(), g), (u2.value().clone(), q.clone())])
+        .to_affine();
+    if point.is_infinity() || Scalar::new(&scalars, point.x.value).value() != &sig.r {
+        return Err(Error::InvalidSignature);
+    }
+    Ok(())
+}
+
+// SEC 1 section 4.1.6, `R` is lifted from `x = r + jn` where `j` is bit 1 of `recovery_id`
+// and `y` of the parity in bit 0, then `Q = r^-1 (sR - zG)`
+pub fn recover_public_key<'a>(
+    curve: &WeierstrassCurve<'a>,
+    msg_hash: &[u8],
+    sig: &Signature,
+    recovery_id: u8,
+) -> Result<Point<'a>, Error> {
+    let scalars = curve.scalar_field()?;
+    let g = generator(curve)?;
+    let field = curve.a().field;
+    if recovery_id > 3 || sig.r.is_zero() || sig.s.is_zero() || sig.r >= scalars.order || sig.s >= scalars.order {
+        return Err(Error::InvalidSignature);
+    }
+
+    let x = if recovery_id & 2 != 0 {
+        &sig.r + &scalars.order
+    } else {
+        sig.r.clone()
+    };
+    // `r + n` only exists when `n < p`, and with probability about `(p - n) / p`
+    if x >= field.order {
+        return Err(Error::InvalidSignature);
+    }
+    let x = field.get(x);
+    let (y1, y2) = curve.evaluate_y(x.clone()).ok_or(Error::InvalidSignature)?;
+    let odd = recovery_id & 1 != 0;
+    let y = if y1.value.bit(0) == odd { y1 } else { y2 };
+    let point = Point { x, y, z: field.one() };
+    if !curve.is_in_prime_subgroup(&point)? {
+        return Err(Error::InvalidSignature);
+    }
+
+    let z = Scalar::new(&scalars, bits2int(msg_hash, &scalars.order));
+    let r_inv = Scalar::new(&scalars, sig.r.clone()).invert().ok_or(Error::InvalidSignature)?;
+    let u1 = -(z * r_inv.clone());
+    let u2 = Scalar::new(&scalars, sig.s.clone()) * r_inv;
+
+    let q = curve
+        .multi_scalar_mul(&[(u1.value().clone(), g), (u2.value().clone(), point)])
+        .to_affine();
+    if q.is_infinity() {
+        return Err(Error::InvalidSignature);
+    }
+    Ok(q.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use sha2::{Sha256, Sha384, Sha512};
+    use sha3::Keccak256;
+    use crate::curves::named::{hex, P256, SECP256K1};
+    use crate::test_utils::hex_bytes;
+    use super::*;
+
+    fn signature(r: &str, s: &str) -> Signature {
+        Signature { r: hex(r), s: hex(s) }
+    }
+
+    // rfc6979 A.2.5
+    #[test]
+    fn rfc6979_p256() {
+        let field = P256.field();
+        let e = P256.curve(&field);
+        let scalars = e.scalar_field().unwrap();
+        let d = hex("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721");
+        let q = public_key(&e, &d).unwrap();
+        assert_eq!(q, Point {
+            x: field.get(hex("60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6")),
+            y: field.get(hex("7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299")),
+            z: field.one(),
+        });
+
+        let h = Sha256::digest(b"sample");
+        assert_eq!(
+            Rfc6979::<Sha256>::new(&scalars, &d, &h).next(&scalars.order),
+            hex("a6e3c57dd01abe90086538398355dd4c3b17aa873382b0f24d6129493d8aad60"),
+        );
+        let sig = sign::<Sha256>(&e, &d, &h).unwrap();
+        assert_eq!(sig, signature(
+            "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716",
+            "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8",
+        ));
+        assert_eq!(verify(&e, &q, &h, &sig), Ok(()));
+
+        let h = Sha256::digest(b"test");
+        let sig = sign::<Sha256>(&e, &d, &h).unwrap();
+        assert_eq!(sig, signature(
+            "f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367",
+            "019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083",
+        ));
+        assert_eq!(verify(&e, &q, &h, &sig), Ok(()));
+
+        let h = Sha512::digest(b"sample");
+        assert_eq!(
+            Rfc6979::<Sha512>::new(&scalars, &d, &h).next(&scalars.order),
+            hex("5fa81c63109badb88c1f367b47da606da28cad69aa22c4fe6ad7df73a7173aa5"),
+        );
+        let sig = sign::<Sha512>(&e, &d, &h).unwrap();
+        assert_eq!(sig, signature(
+            "8496a60b5e9b47c825488827e0495b0e3fa109ec4568fd3f8d1097678eb97f00",
+            "2362ab1adbe2b8adf9cb9edab740ea6049c028114f2460f96554f61fae3302fe",
+        ));
+        assert_eq!(verify(&e, &q, &h, &sig), Ok(()));
+
+        // SHA-384 hashes are longer than `n` and get truncated to their leftmost 256 bits
+        let h = Sha384::digest(b"sample");
+        assert_eq!(
+            Rfc6979::<Sha384>::new(&scalars, &d, &h).next(&scalars.order),
+            hex("09f634b188cefd98e7ec88b1aa9852d734d0bc272f7d2a47decc6ebeb375aad4"),
+        );
+        let sig = sign::<Sha384>(&e, &d, &h).unwrap();
+        assert_eq!(sig, signature(
+            "0eafea039b20e9b42309fb1d89e213057cbf973dc0cfc8f129edddc800ef7719",
+            "4861f0491e6998b9455193e34e7b0d284ddd7149a74b95b9261f13abde940954",
+        ));
+        assert_eq!(verify(&e, &q, &h, &sig), Ok(()));
+
+        let h = Sha384::digest(b"test");
+        assert_eq!(
+            Rfc6979::<Sha384>::new(&scalars, &d, &h).next(&scalars.order),
+            hex("16aeffa357260b04b1dd199693960740066c1a8f3e8edd79070aa914d361b3b8"),
+        );
+        let sig = sign::<Sha384>(&e, &d, &h).unwrap();
+        assert_eq!(sig, signature(
+            "83910e8b48bb0c74244ebdf7f07a1c5413d61472bd941ef3920e623fbccebeb6",
+            "8ddbec54cf8cd5874883841d712142a56a8d0f218f5003cb0296b6b509619f2c",
+        ));
+        assert_eq!(verify(&e, &q, &h, &sig), Ok(()));
+    }
+
+    #[test]
+    fn rfc6979_secp256k1() {
+        let field = SECP256K1.field();
+        let e = SECP256K1.curve(&field);
+        let scalars = e.scalar_field().unwrap();
+        let h = Sha256::digest(b"Satoshi Nakamoto");
+
+        let d = BigUint::from(1_u8);
+        assert_eq!(
+            Rfc6979::<Sha256>::new(&scalars, &d, &h).next(&scalars.order),
+            hex("8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15"),
+        );
+        let sig = sign::<Sha256>(&e, &d, &h).unwrap();
+        assert!(!sig.is_low_s(&scalars));
+        assert_eq!(sig.normalize_s(&scalars), signature(
+            "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8",
+            "2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5",
+        ));
+        let q = public_key(&e, &d).unwrap();
+        assert_eq!(verify(&e, &q, &h, &sig), Ok(()));
+        assert_eq!(verify(&e, &q, &h, &sig.normalize_s(&scalars)), Ok(()));
+
+        let d = scalars.order.clone() - BigUint::from(1_u8);
+        let sig = sign::<Sha256>(&e, &d, &h).unwrap().normalize_s(&scalars);
+        assert_eq!(sig, signature(
+            "fd567d121db66e382991534ada77a6bd3106f0a1098c231e47993447cd6af2d0",
+            "6b39cd0eb1bc8603e159ef5c20a5c8ad685a45b06ce9bebed3f153d10d93bed5",
+        ));
+        assert_eq!(verify(&e, &public_key(&e, &d).unwrap(), &h, &sig), Ok(()));
+
+        // Ethereum transaction, keccak-256 message hash with HMAC-SHA-256 nonces
+        let d = hex("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+        let h = Keccak256::digest(hex_bytes("e9808504e3b29200831e848094f0109fc8df283027b6285cc889f5aa624eac1f55843b9aca0080018080"));
+        let sig = sign::<Sha256>(&e, &d, &h).unwrap().normalize_s(&scalars);
+        assert_eq!(
+            sig.to_bytes(&scalars),
+            hex_bytes("c9cf86333bcb065d140032ecaab5d9281bde80f21b9687b3e94161de42d51895727a108a0b8d101465414033c3f705a9c7b826e596766046ee1183dbc8aeaa68"),
+        );
+    }
+
+    // fixed nonce vector from the `k256` crate
+    #[test]
+    fn fixed_nonce() {
+        let field = SECP256K1.field();
+        let e = SECP256K1.curve(&field);
+        let d = hex("ebb2c082fd7727890a28ac82f6bdf97bad8de9f5d7c9028692de1a255cad3e0f");
+        let h = hex_bytes("4b688df40bcedbe641ddb16ff0a1842d9c67ea1c3bf63f3e0471baa664531d1a");
+        let k = hex("49a0d7b786ec9cde0d0721d72804befd06571c974b191efb42ecf322ba9ddd9a");
+
+        let q = public_key(&e, &d).unwrap();
+        assert_eq!(q, Point {
+            x: field.get(hex("779dd197a5df977ed2cf6cb31d82d43328b790dc6b3b7d4437a427bd5847dfcd")),
+            y: field.get(hex("e94b724a555b6d017bb7607c3e3281daf5b1699d6ef4124975c9237b917d426f")),
+            z: field.one(),
+        });
+        let (sig, _) = sign_with_nonce(&e, &d, &h, &k).unwrap().unwrap();
+        assert_eq!(sig, signature(
+            "241097efbf8b63bf145c8961dbdf10c310efbb3b2676bbc0f8b08505c9e2f795",
+            "021006b7838609339e8b415a7f9acb1b661828131aef1ecbc7955dfb01f3ca0e",
+        ));
+        assert_eq!(verify(&e, &q, &h, &sig), Ok(()));
+    }
+
+    #[test]
+    fn rejects() {
+        let field = P256.field();
+        let e = P256.curve(&field);
+        let n = e.order().unwrap().clone();
+        let rng = &mut StdRng::seed_from_u64(5);
+        let (d, q) = generate_keypair_with(&e, rng).unwrap();
+        let h = Sha256::digest(b"message");
+        let sig = sign::<Sha256>(&e, &d, &h).unwrap();
+        assert_eq!(verify(&e, &q, &h, &sig), Ok(()));
+
+        assert_eq!(verify(&e, &q, &Sha256::digest(b"massage"), &sig), Err(Error::InvalidSignature));
+        let (_, other) = generate_keypair_with(&e, rng).unwrap();
+        assert_eq!(verify(&e, &other, &h, &sig), Err(Error::InvalidSignature));
+        let swapped = Signature { r: sig.s.clone(), s: sig.r.clone() };
+        assert_eq!(verify(&e, &q, &h, &swapped), Err(Error::InvalidSignature));
+
+        let zero = Signature { r: BigUint::zero(), s: sig.s.clone() };
+        assert_eq!(verify(&e, &q, &h, &zero), Err(Error::InvalidSignature));
+        let overflow = Signature { r: sig.r.clone(), s: sig.s.clone() + n.clone() };
+        assert_eq!(verify(&e, &q, &h, &overflow), Err(Error::InvalidSignature));
+
+        assert_eq!(verify(&e, &Point::infinity(&field), &h, &sig), Err(Error::SmallOrder));
+        let off_curve = Point { y: q.y.clone() + field.one(), ..q.clone() };
+        assert_eq!(verify(&e, &off_curve, &h, &sig), Err(Error::NotOnCurve));
+
+        assert_eq!(sign::<Sha256>(&e, &BigUint::zero(), &h), Err(Error::InvalidScalar));
+        assert_eq!(sign::<Sha256>(&e, &n, &h), Err(Error::InvalidScalar));
+        assert_eq!(public_key(&e, &n), Err(Error::InvalidScalar));
+
+        let bare = WeierstrassCurve::new(e.a().clone(), e.b().clone())
+            .with_order(n.clone(), BigUint::from(1_u8));
+        assert_eq!(public_key(&bare, &d), Err(Error::UnknownGenerator));
+        assert_eq!(sign::<Sha256>(&bare, &d, &h), Err(Error::UnknownGenerator));
+    }
+
+    #[test]
+    fn low_s() {
+        let field = SECP256K1.field();
+        let e = SECP256K1.curve(&field);
+        let scalars = e.scalar_field().unwrap();
+        let half: BigUint = &scalars.order >> 1;
+
+        let sig = Signature { r: BigUint::from(1_u8), s: half.clone() };
+        assert!(sig.is_low_s(&scalars));
+        assert_eq!(sig.normalize_s(&scalars), sig);
+
+        let sig = Signature { r: BigUint::from(1_u8), s: half.clone() + BigUint::from(1_u8) };
+        assert!(!sig.is_low_s(&scalars));
+        assert_eq!(sig.normalize_s(&scalars), Signature { r: BigUint::from(1_u8), s: half });
+    }
+
+    #[test]
+    fn encoding() {
+        let field = P256.field();
+        let e = P256.curve(&field);
+        let scalars = e.scalar_field().unwrap();
+        let sig = signature(
+            "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716",
+            "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8",
+        );
+
+        let fixed = sig.to_bytes(&scalars);
+        assert_eq!(fixed.len(), 64);
+        assert_eq!(Signature::from_bytes(&scalars, &fixed), Ok(sig.clone()));
+        assert_eq!(Signature::from_bytes(&scalars, &fixed[1..]), Err(Error::InvalidEncoding));
+        assert_eq!(Signature::from_bytes(&scalars, &[0xff; 64]), Err(Error::InvalidEncoding));
+
+        let der = sig.to_der();
+        assert_eq!(der, hex_bytes("3046022100efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716022100f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"));
+        assert_eq!(Signature::from_der(&der), Ok(sig));
+
+        let small = Signature { r: BigUint::from(1_u8), s: BigUint::from(0x80_u8) };
+        assert_eq!(small.to_der(), hex_bytes("300702010102020080"));
+        assert_eq!(Signature::from_der(&small.to_der()), Ok(small));
+
+        for bad in [
+            "",
+            "3007020101020101",             // sequence length too long
+            "30060201010201010000",         // trailing bytes
+            "3106020101020101",             // wrong tag
+            "300702020001020101",           // non-minimal integer
+            "3006020181020101",             // negative integer
+            "30050201010200",               // empty integer
+            "308106020101020101",           // long form for a short length
+        ] {
+            assert_eq!(Signature::from_der(&hex_bytes(bad)), Err(Error::InvalidEncoding), "{bad}");
+        }
+    }
+
+    #[test]
+    fn long_der() {
+        // P-521 signatures need the long form of the sequence length
+        let sig = Signature {
+            r: (BigUint::from(1_u8) << 520) - BigUint::from(1_u8),
+            s: (BigUint::from(1_u8) << 520) - BigUint::from(1_u8),
+        };
+        let der = sig.to_der();
+        assert_eq!(der[..3], [0x30, 0x81, 0x88]);
+        assert_eq!(Signature::from_der(&der), Ok(sig));
+    }
+
+    #[test]
+    fn round_trip() {
+        let rng = &mut StdRng::seed_from_u64(11);
+        for params in [P256, SECP256K1] {
+            let field = params.field();
+            let e = params.curve(&field);
+            let (d, q) = generate_keypair_with(&e, rng).unwrap();
+            assert_eq!(e.validate(&q), Ok(()));
+            for msg in [b"".as_slice(), b"abc", &[0xff; 100]] {
+                let h = Sha256::digest(msg);
+                let sig = sign::<Sha256>(&e, &d, &h).unwrap();
+                assert_eq!(sign::<Sha256>(&e, &d, &h), Ok(sig.clone()));
+                assert_eq!(verify(&e, &q, &h, &sig), Ok(()));
+                assert_eq!(verify(&e, &q, &h, &Signature::from_der(&sig.to_der()).unwrap()), Ok(()));
+            }
+        }
+        let field = P256.field();
+        let e = P256.curve(&field);
+        assert!(generate_keypair(&e).is_ok());
+    }
+
+    // vectors from the `k256` crate
+    #[test]
+    fn recovery() {
+        let field = SECP256K1.field();
+        let e = SECP256K1.curve(&field);
+        let scalars = e.scalar_field().unwrap();
+        let h = Sha256::digest(b"example message");
+
+        for (pk, sig, id) in [
+            (
+                "021a7a569e91dbf60581509c7fc946d1003b60c7dee85299538db6353538d59574",
+                "ce53abb3721bafc561408ce8ff99c909f7f0b18a2f788649d6470162ab1aa0323971edc523a6d6453f3fb6128d318d9db1a5ff3386feb1047d9816e780039d52",
+                0,
+            ),
+            (
+                "036d6caac248af96f6afa7f904f550253a0f3ef3f5aa2fe6838a95b216691468e2",
+                "46c05b6368a44b8810d79859441d819b8e7cdc8bfd371e35c53196f4bcacdb5135c7facce2a97b95eacba8a586d87b7958aaf8368ab29cee481f76e871dbd9cb",
+                1,
+            ),
+        ] {
+            let sig = Signature::from_bytes(&scalars, &hex_bytes(sig)).unwrap();
+            let q = recover_public_key(&e, &h, &sig, id).unwrap();
+            assert_eq!(e.encode_point(&q, true), hex_bytes(pk));
+            assert_eq!(verify(&e, &q, &h, &sig), Ok(()));
+            assert_ne!(recover_public_key(&e, &h, &sig, id ^ 1), Ok(q));
+        }
+
+        // Ethereum transaction from `rfc6979_secp256k1`
+        let d = hex("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318");
+        let h = Keccak256::digest(hex_bytes("e9808504e3b29200831e848094f0109fc8df283027b6285cc889f5aa624eac1f55843b9aca0080018080"));
+        let (sig, id) = sign_recoverable::<Sha256>(&e, &d, &h).unwrap();
+        assert_eq!(sig, sign::<Sha256>(&e, &d, &h).unwrap().normalize_s(&scalars));
+        assert_eq!(id, 0);
+        assert_eq!(recover_public_key(&e, &h, &sig, id), public_key(&e, &d));
+    }
+
+    #[test]
+    fn recovery_round_trip() {
+        let rng = &mut StdRng::seed_from_u64(13);
+        for params in [P256, SECP256K1] {
+            let field = params.field();
+            let e = params.curve(&field);
+            let scalars = e.scalar_field().unwrap();
+            let (d, q) = generate_keypair_with(&e, rng).unwrap();
+            let mut ids = [false; 2];
+            for i in 0..8_u8 {
+                let h = Sha256::digest([i]);
+                let (sig, id) = sign_recoverable::<Sha256>(&e, &d, &h).unwrap();
+                assert!(sig.is_low_s(&scalars));
+                ids[id as usize] = true;
+                assert_eq!(recover_public_key(&e, &h, &sig, id), Ok(q.clone()));
+            }
+            // both parities of `R` show up within a few messages
+            assert_eq!(ids, [true, true]);
+        }
+    }
+
+    // `x([k]G) >= n` happens with probability about `2^-128` on secp256k1, so `R` is picked
+    // first and the public key is derived from an arbitrary `s` and hash
+    #[test]
+    fn recovery_overflow() {
+        let field = SECP256K1.field();
+        let e = SECP256K1.curve(&field);
+        let scalars = e.scalar_field().unwrap();
+        let g = e.get_base();
+
+        let mut x = field.get(scalars.order.clone() + BigUint::from(1_u8));
+        let (y, _) = loop {
+            if let Some(y) = e.evaluate_y(x.clone()) {
+                break y;
+            }
+            x = x + field.one();
+        };
+        let y = if y.value.bit(0) { -y } else { y };
+        let point = Point { x: x.clone(), y, z: field.one() };
+
+        let h = Sha256::digest(b"overflow");
+        let sig = Signature {
+            r: x.value - &scalars.order,
+            s: BigUint::from(12345_u16),
+        };
+        // `Q = r^-1 (sR - zG)`
+        let r_inv = Scalar::new(&scalars, sig.r.clone()).invert().unwrap();
+        let z = Scalar::new(&scalars, bits2int(&h, &scalars.order));
+        let q = e.multi_scalar_mul(&[
+            ((Scalar::new(&scalars, sig.s.clone()) * r_inv.clone()).value().clone(), point),
+            ((-(z * r_inv)).value().clone(), g),
+        ]);
+
+        assert_eq!(verify(&e, &q, &h, &sig), Ok(()));
+        assert_eq!(recover_public_key(&e, &h, &sig, 2), Ok(q.clone()));
+        assert_ne!(recover_public_key(&e, &h, &sig, 0), Ok(q));
+
+        // `r + n >= p` for large `r`
+        let sig = Signature { r: scalars.order.clone() - BigUint::from(1_u8), s: BigUint::from(1_u8) };
+        assert_eq!(recover_public_key(&e, &h, &sig, 2), Err(Error::InvalidSignature));
+        assert_eq!(recover_public_key(&e, &h, &sig, 4), Err(Error::InvalidSignature));
+        let sig = Signature { r: BigUint::zero(), s: BigUint::from(1_u8) };
+        assert_eq!(recover_public_key(&e, &h, &sig, 0), Err(Error::InvalidSignature));
+    }
+}
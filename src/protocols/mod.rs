@@ -0,0 +1,2 @@
+pub mod ecdh;
+pub mod ecdsa;
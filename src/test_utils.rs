@@ -0,0 +1,32 @@
+use num_bigint::BigUint;
+use crate::curves::point::Point;
+use crate::curves::weierstass::WeierstrassCurve;
+use crate::field::field::Field;
+use crate::field::field_element::FieldElement;
+
+// big-endian hex digits without prefix, two per byte
+pub(crate) fn hex_bytes(s: &str) -> Vec<u8> {
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+}
+
+// every affine `(x, y)` that satisfies `on_curve`, by brute force over a small prime field
+pub(crate) fn affine_points<'a>(
+    field: &'a Field,
+    on_curve: impl Fn(&FieldElement<'a>, &FieldElement<'a>) -> bool,
+) -> Vec<(FieldElement<'a>, FieldElement<'a>)> {
+    let p = u16::try_from(&field.order).expect("field should be small enough to enumerate");
+    (0..p)
+        .flat_map(|x| (0..p).map(move |y| (field.get(BigUint::from(x)), field.get(BigUint::from(y)))))
+        .filter(|(x, y)| on_curve(x, y))
+        .collect()
+}
+
+// point of infinity followed by every affine point of the curve
+pub(crate) fn weierstrass_points<'a>(e: &WeierstrassCurve<'a>) -> Vec<Point<'a>> {
+    let field = e.a().field;
+    let mut points = vec![Point::infinity(field)];
+    for (x, y) in affine_points(field, |x, y| e.contains(&Point { x: x.clone(), y: y.clone(), z: field.one() })) {
+        points.push(Point { x, y, z: field.one() });
+    }
+    points
+}
@@ -1,5 +1,3 @@
-use std::usize;
-
 #[derive(Debug, PartialOrd, PartialEq)]
 pub struct BitIter<T>{
   done: bool,
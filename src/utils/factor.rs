@@ -0,0 +1,160 @@
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use crate::utils::gcd::u_gcd;
+
+const TRIAL_DIVISION_BOUND: u32 = 1 << 16;
+const RHO_ITERATIONS: usize = 1 << 20;
+const WITNESSES: [u8; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+// Miller-Rabin with the first 12 primes as witnesses,
+// deterministic for `n < ψ12 = 318665857834031151167461 ≈ 3.18 * 10^23`, probabilistic above that
+pub fn is_probable_prime (n: &BigUint) -> bool {
+  let one = BigUint::one();
+  let two = BigUint::from(2_u8);
+  if n < &two {
+    return false;
+  }
+  for w in WITNESSES {
+    let w = BigUint::from(w);
+    if n == &w {
+      return true;
+    }
+    if (n % &w).is_zero() {
+      return false;
+    }
+  }
+
+  let n_1 = n - &one;
+  let s = n_1.trailing_zeros().unwrap_or(0);
+  let d = &n_1 >> s;
+
+  'witness: for w in WITNESSES {
+    let mut x = BigUint::from(w).modpow(&d, n);
+    if x == one || x == n_1 {
+      continue;
+    }
+    for _ in 1..s {
+      x = (&x * &x) % n;
+      if x == n_1 {
+        continue 'witness;
+      }
+    }
+    return false;
+  }
+  true
+}
+
+// Pollard's rho with Brent's cycle detection, gives up after `RHO_ITERATIONS`
+pub fn pollard_rho (n: &BigUint) -> Option<BigUint> {
+  let one = BigUint::one();
+  if (n % 2_u8).is_zero() {
+    return Some(BigUint::from(2_u8));
+  }
+
+  for c in 1_u32..=8 {
+    let c = BigUint::from(c);
+    let f = |x: &BigUint| (x * x + &c) % n;
+
+    let mut y = BigUint::from(2_u8);
+    let mut g = one.clone();
+    let mut r = 1;
+    let mut iterations = 0;
+
+    while g.is_one() && iterations < RHO_ITERATIONS {
+      let x = y.clone();
+      for _ in 0..r {
+        y = f(&y);
+      }
+      let mut k = 0;
+      while k < r && g.is_one() {
+        y = f(&y);
+        let d = if x > y { &x - &y } else { &y - &x };
+        g = u_gcd(&d, n);
+        k += 1;
+      }
+      iterations += r;
+      r *= 2;
+    }
+
+    if !g.is_one() && &g != n {
+      return Some(g);
+    }
+  }
+  None
+}
+
+// factors `n` into `(prime, exponent)` pairs in ascending order,
+// second value is the part that could not be factored (`1` if factorization is complete)
+pub fn factorize (n: &BigUint) -> (Vec<(BigUint, u32)>, BigUint) {
+  let mut factors: Vec<(BigUint, u32)> = vec![];
+  let mut unfactored = BigUint::one();
+  let mut n = n.clone();
+
+  let push = |factors: &mut Vec<(BigUint, u32)>, p: BigUint| {
+    match factors.iter_mut().find(|(f, _)| f == &p) {
+      Some((_, e)) => *e += 1,
+      None => factors.push((p, 1)),
+    }
+  };
+
+  let mut i = 2_u32;
+  while i < TRIAL_DIVISION_BOUND && BigUint::from(i) * BigUint::from(i) <= n {
+    while (&n % i).is_zero() {
+      push(&mut factors, BigUint::from(i));
+      n /= i;
+    }
+    i += 1;
+  }
+
+  let mut stack = vec![];
+  if n > BigUint::one() {
+    stack.push(n);
+  }
+  while let Some(m) = stack.pop() {
+    if is_probable_prime(&m) {
+      push(&mut factors, m);
+      continue;
+    }
+    match pollard_rho(&m) {
+      Some(d) => {
+        stack.push(&m / &d);
+        stack.push(d);
+      }
+      None => unfactored *= m,
+    }
+  }
+
+  factors.sort();
+  (factors, unfactored)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn primality () {
+    assert!(!is_probable_prime(&BigUint::from(1_u8)));
+    assert!(is_probable_prime(&BigUint::from(2_u8)));
+    assert!(is_probable_prime(&BigUint::from(631_u16)));
+    assert!(!is_probable_prime(&BigUint::from(561_u16))); // carmichael number
+    assert!(is_probable_prime(&BigUint::from(1_u128 + 407 * (1 << 119))));
+    assert!(!is_probable_prime(&(BigUint::from(1_u128 + 407 * (1 << 119)) * BigUint::from(1000000007_u32))));
+  }
+
+  #[test]
+  fn factors () {
+    let n = BigUint::from(2_u32 * 2 * 3 * 631 * 631);
+    assert_eq!(factorize(&n), (
+      vec![(BigUint::from(2_u8), 2), (BigUint::from(3_u8), 1), (BigUint::from(631_u16), 2)],
+      BigUint::one(),
+    ));
+
+    // both factors are above trial division bound
+    let n = BigUint::from(1000000007_u64 * 998244353);
+    assert_eq!(factorize(&n), (
+      vec![(BigUint::from(998244353_u32), 1), (BigUint::from(1000000007_u32), 1)],
+      BigUint::one(),
+    ));
+  }
+}
@@ -1,3 +1,6 @@
+use num_bigint::BigUint;
+use num_traits::Zero;
+
 pub fn gcd (a: u128, b: u128) -> u128 {
   let mut a = a;
   let mut b = b;
@@ -9,9 +12,23 @@ pub fn gcd (a: u128, b: u128) -> u128 {
   a
 }
 
+// same for numbers that do not fit into `u128`
+pub fn u_gcd (a: &BigUint, b: &BigUint) -> BigUint {
+  let mut a = a.clone();
+  let mut b = b.clone();
+  while !b.is_zero() {
+    let t = &a % &b;
+    a = b;
+    b = t;
+  }
+  a
+}
+
 #[cfg(test)]
 mod tests {
-  use crate::utils::gcd::gcd;
+  use num_bigint::BigUint;
+  use crate::field::field::get_field_prime;
+  use crate::utils::gcd::{gcd, u_gcd};
 
   #[test]
   fn test () {
@@ -19,4 +36,10 @@ mod tests {
     assert_eq!(gcd(240, 46), 2);
     assert_eq!(gcd(3, 1_u128 + 407 * (1 << 119)), 1);
   }
+
+  #[test]
+  fn test_u_gcd () {
+    assert_eq!(u_gcd(&BigUint::from(240_u8), &BigUint::from(46_u8)), BigUint::from(2_u8));
+    assert_eq!(u_gcd(&(get_field_prime() * 6_u8), &(get_field_prime() * 4_u8)), get_field_prime() * 2_u8);
+  }
 }
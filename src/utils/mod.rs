@@ -1,4 +1,6 @@
 pub mod bit_iter;
+pub mod factor;
 pub mod gcd;
 pub mod s_tonelli;
+pub mod wnaf;
 pub mod xgcd;
@@ -17,6 +17,9 @@ pub fn tonelli_shanks(x: FieldElement<'_>) -> Option<(FieldElement<'_>, FieldEle
     let mut ss: u128 = 0;
     let mut z = FieldElement::new(x.field, big_two.clone());
 
+    if x.is_zero() {
+        return Some((x.clone(), x));
+    }
     if x.clone() ^ ((p.clone() - big_one.clone()) / big_two.clone()) != one {
         return None;
     }
@@ -86,5 +89,7 @@ mod tests {
         run(100049, 44402, Some((69581, 30468)));
         run(1000000009, 665820697, Some((621366697, 378633312)));
         run(1000000000039, 881398088036, Some((208600591990, 791399408049)));
+        run(13, 0, Some((0, 0)));
+        run(13, 5, None);
     }
 }
@@ -0,0 +1,77 @@
+use num_bigint::BigUint;
+use num_traits::Zero;
+
+// width-w non-adjacent form, least significant digit first.
+// Every non-zero digit is odd with `|d| < 2^(w-1)` and is followed by at least `w - 1` zeros,
+// so `k = Σ d_i 2^i` has about `bits / (w + 1)` non-zero digits. Width is clamped to `2..=16`
+pub fn wnaf(k: &BigUint, w: usize) -> Vec<i32> {
+  let w = w.clamp(2, 16);
+  let modulus = 1_i32 << w;
+  let mask = BigUint::from((modulus - 1) as u32);
+
+  let mut k = k.clone();
+  let mut digits = Vec::with_capacity(k.bits() as usize + 1);
+  while !k.is_zero() {
+    let mut d = 0;
+    if k.bit(0) {
+      // `k mods 2^w`, residue in `(-2^(w-1), 2^(w-1))`
+      d = u32::try_from(&k & &mask).unwrap() as i32;
+      if d >= modulus / 2 {
+        d -= modulus;
+      }
+      if d > 0 {
+        k -= BigUint::from(d as u32);
+      } else {
+        k += BigUint::from(d.unsigned_abs());
+      }
+    }
+    digits.push(d);
+    k >>= 1;
+  }
+  digits
+}
+
+#[cfg(test)]
+mod tests {
+  use num_bigint::{BigInt, BigUint};
+  use super::*;
+
+  fn value (digits: &[i32]) -> BigInt {
+    digits.iter().rev().fold(BigInt::zero(), |acc, d| acc * 2 + d)
+  }
+
+  #[test]
+  fn small () {
+    // 7 = 8 - 1
+    assert_eq!(wnaf(&BigUint::from(7_u8), 2), vec![-1, 0, 0, 1]);
+    // 29 = 32 - 3
+    assert_eq!(wnaf(&BigUint::from(29_u8), 3), vec![-3, 0, 0, 0, 0, 1]);
+    assert_eq!(wnaf(&BigUint::zero(), 4), Vec::<i32>::new());
+  }
+
+  #[test]
+  fn clamps_width () {
+    let k = BigUint::from(0xDEADBEEF_u32);
+    assert_eq!(wnaf(&k, 0), wnaf(&k, 2));
+    assert_eq!(wnaf(&k, 1), wnaf(&k, 2));
+    assert_eq!(wnaf(&k, 40), wnaf(&k, 16));
+  }
+
+  #[test]
+  fn recodes () {
+    let k = BigUint::parse_bytes(b"FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364140", 16).unwrap();
+    for w in 2..=8 {
+      let digits = wnaf(&k, w);
+      assert_eq!(value(&digits), BigInt::from(k.clone()));
+      assert!(digits.len() as u64 <= k.bits() + 1);
+
+      let bound = 1 << (w - 1);
+      for (i, d) in digits.iter().enumerate() {
+        if *d != 0 {
+          assert!(d % 2 != 0 && d.abs() < bound);
+          assert!(digits[i + 1..].iter().take(w - 1).all(|d| *d == 0));
+        }
+      }
+    }
+  }
+}
@@ -46,7 +46,7 @@ pub fn u_xgcd (a: BigUint, b: BigUint) -> (BigInt, BigInt, BigUint) {
     a = (a.1.clone(), a.0 - q.clone() * a.1.clone())
   }
 
-  (xy1.0, xy1.1, a.0.into())
+  (xy1.0, xy1.1, a.0)
 }
 
 #[cfg(test)]